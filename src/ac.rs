@@ -0,0 +1,207 @@
+//! AC effective impedance (NEC Chapter 9 Table 9)
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Awg, Error, WireGauge, WireSize};
+
+/// Default power factor for AC circuits
+pub const DEFAULT_POWER_FACTOR: f64 = 0.85;
+
+/// Raceway the conductors run in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conduit {
+    /// PVC (non-metallic) conduit
+    #[default]
+    Pvc,
+    /// Aluminum conduit
+    Aluminum,
+    /// Steel conduit
+    Steel,
+}
+
+impl Conduit {
+    /// Name accepted on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Conduit::Pvc => "pvc",
+            Conduit::Aluminum => "aluminum",
+            Conduit::Steel => "steel",
+        }
+    }
+}
+
+impl fmt::Display for Conduit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Conduit::Pvc => "PVC",
+            Conduit::Aluminum => "Aluminum",
+            Conduit::Steel => "Steel",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Conduit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pvc" => Ok(Conduit::Pvc),
+            "aluminum" | "aluminium" | "al" => Ok(Conduit::Aluminum),
+            "steel" => Ok(Conduit::Steel),
+            _ => Err(Error::InvalidConduit(s.to_string())),
+        }
+    }
+}
+
+/// AC circuit parameters for the effective impedance method
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AcParameters {
+    /// Load power factor, from 0 to 1
+    pub power_factor: f64,
+    /// Raceway the conductors run in
+    pub conduit: Conduit,
+}
+
+impl Default for AcParameters {
+    fn default() -> Self {
+        AcParameters {
+            power_factor: DEFAULT_POWER_FACTOR,
+            conduit: Conduit::default(),
+        }
+    }
+}
+
+impl AcParameters {
+    /// AC resistance in ohms per 1000 feet, from the DC resistance of `gauge`
+    pub fn resistance(&self, gauge: &WireGauge, dc_resistance: f64) -> f64 {
+        let ratio = match self.conduit {
+            Conduit::Pvc => interpolate(gauge.area, |row| row.3),
+            Conduit::Aluminum => interpolate(gauge.area, |row| row.4),
+            Conduit::Steel => interpolate(gauge.area, |row| row.5),
+        };
+        dc_resistance * ratio
+    }
+
+    /// Inductive reactance in ohms per 1000 feet
+    pub fn reactance(&self, gauge: &WireGauge) -> f64 {
+        match self.conduit {
+            Conduit::Pvc | Conduit::Aluminum => interpolate(gauge.area, |row| row.1),
+            Conduit::Steel => interpolate(gauge.area, |row| row.2),
+        }
+    }
+
+    /// Effective impedance in ohms per 1000 feet: Z = R × cos θ + X × sin θ
+    pub fn effective_impedance(&self, resistance: f64, reactance: f64) -> f64 {
+        let sin = (1.0 - self.power_factor * self.power_factor).max(0.0).sqrt();
+        resistance * self.power_factor + reactance * sin
+    }
+}
+
+type Table9Row = (WireSize, f64, f64, f64, f64, f64);
+
+// NEC Chapter 9 Table 9, 600 V cables, three-phase, 60 Hz, 75°C
+// Format: (size, XL PVC/aluminum conduit, XL steel conduit,
+//          AC/DC resistance ratio in PVC, aluminum and steel conduit)
+// Reactances are in ohms to neutral per 1000 feet. The resistance ratios
+// compare the Table 9 uncoated copper AC resistances with the Table 8 DC
+// resistances, and carry the skin and proximity effects of large conductors.
+// Up to 2/0 the difference is within the rounding of Table 9.
+const TABLE_9: &[Table9Row] = &[
+    (WireSize::Awg(Awg::new(14)), 0.058, 0.073, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(12)), 0.054, 0.068, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(10)), 0.050, 0.063, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(8)), 0.052, 0.065, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(6)), 0.051, 0.064, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(4)), 0.048, 0.060, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(3)), 0.047, 0.059, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(2)), 0.045, 0.057, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(1)), 0.046, 0.057, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(0)), 0.044, 0.055, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(-1)), 0.043, 0.054, 1.00, 1.00, 1.00),
+    (WireSize::Awg(Awg::new(-2)), 0.042, 0.052, 1.01, 1.07, 1.03),
+    (WireSize::Awg(Awg::new(-3)), 0.041, 0.051, 1.02, 1.10, 1.04),
+    (WireSize::Kcmil(250), 0.041, 0.052, 1.01, 1.11, 1.05),
+    (WireSize::Kcmil(300), 0.041, 0.051, 1.02, 1.14, 1.05),
+    (WireSize::Kcmil(350), 0.040, 0.050, 1.03, 1.17, 1.06),
+    (WireSize::Kcmil(400), 0.040, 0.049, 1.03, 1.18, 1.09),
+    (WireSize::Kcmil(500), 0.039, 0.048, 1.05, 1.24, 1.12),
+    (WireSize::Kcmil(600), 0.039, 0.048, 1.07, 1.31, 1.17),
+    (WireSize::Kcmil(750), 0.038, 0.048, 1.11, 1.40, 1.23),
+    (WireSize::Kcmil(1000), 0.037, 0.046, 1.16, 1.47, 1.40),
+];
+
+/// Look up a Table 9 value for a conductor of `area` circular mils
+///
+/// Values are interpolated on the logarithm of the area between table
+/// sizes and held at the first or last row outside the table.
+fn interpolate(area: f64, value: impl Fn(&Table9Row) -> f64) -> f64 {
+    let first = &TABLE_9[0];
+    let last = &TABLE_9[TABLE_9.len() - 1];
+    if area <= first.0.area() {
+        return value(first);
+    }
+    if area >= last.0.area() {
+        return value(last);
+    }
+
+    let upper = TABLE_9
+        .iter()
+        .position(|row| row.0.area() >= area)
+        .unwrap_or(TABLE_9.len() - 1);
+    let (low, high) = (&TABLE_9[upper - 1], &TABLE_9[upper]);
+    let t = (area.ln() - low.0.area().ln()) / (high.0.area().ln() - low.0.area().ln());
+    value(low) + (value(high) - value(low)) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gauge::gauge;
+    use crate::{Circuit, System};
+
+    #[test]
+    fn looks_up_table_9_by_conduit() {
+        let pvc = AcParameters::default();
+        let steel = AcParameters {
+            conduit: Conduit::Steel,
+            ..pvc
+        };
+        assert_eq!(pvc.reactance(&gauge("12")), 0.054);
+        assert_eq!(steel.reactance(&gauge("12")), 0.068);
+        assert_eq!(pvc.resistance(&gauge("12"), 2.0), 2.0);
+        assert_eq!(steel.resistance(&gauge("500kcmil"), 1.0), 1.12);
+    }
+
+    #[test]
+    fn interpolates_on_the_log_of_the_area() {
+        let pvc = AcParameters::default();
+        // AWG areas step by a constant ratio, so 9 AWG is halfway between
+        // 10 AWG (0.050) and 8 AWG (0.052)
+        assert!((pvc.reactance(&gauge("9")) - 0.051).abs() < 1e-12);
+        // Held at the first and last rows outside the table
+        assert_eq!(pvc.reactance(&gauge("18")), 0.058);
+        assert_eq!(pvc.reactance(&gauge("2000kcmil")), 0.037);
+    }
+
+    #[test]
+    fn effective_impedance_weights_r_and_x_by_the_power_factor() {
+        // cos θ = 0.85, sin θ = 0.5268: 2 × 0.85 + 0.05 × 0.5268
+        let ac = AcParameters::default();
+        assert!((ac.effective_impedance(2.0, 0.05) - 1.72634).abs() < 1e-5);
+        let unity = AcParameters {
+            power_factor: 1.0,
+            ..ac
+        };
+        assert_eq!(unity.effective_impedance(2.0, 0.05), 2.0);
+
+        // 12 AWG copper at 75°C in PVC: 1.9315 × 0.85 + 0.054 × 0.5268
+        let circuit = Circuit {
+            ac: Some(ac),
+            system: System::SinglePhase2Wire,
+            ..Circuit::new(120.0, 10.0, 100.0)
+        };
+        assert!((circuit.impedance(&gauge("12")) - 1.6702).abs() < 1e-4);
+    }
+}
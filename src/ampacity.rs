@@ -0,0 +1,280 @@
+//! Conductor ampacity (NEC 310.16) and its derating (NEC 310.15)
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Awg, Error, Material, WireGauge, WireSize};
+
+/// Ambient temperature in °C that NEC 310.16 ampacities are based on
+pub const AMBIENT_BASE: f64 = 30.0;
+
+/// Insulation temperature rating, selecting the NEC 310.16 column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsulationRating {
+    /// 60°C (140°F): TW, UF
+    C60,
+    /// 75°C (167°F): RHW, THHW, THWN, XHHW, USE, ZW
+    #[default]
+    C75,
+    /// 90°C (194°F): THHN, THWN-2, XHHW-2, RHW-2, USE-2
+    C90,
+}
+
+impl InsulationRating {
+    /// Every rating, from lowest to highest
+    pub const ALL: &'static [InsulationRating] = &[
+        InsulationRating::C60,
+        InsulationRating::C75,
+        InsulationRating::C90,
+    ];
+
+    /// Rating in °C
+    pub fn celsius(&self) -> u32 {
+        match self {
+            InsulationRating::C60 => 60,
+            InsulationRating::C75 => 75,
+            InsulationRating::C90 => 90,
+        }
+    }
+}
+
+impl fmt::Display for InsulationRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°C", self.celsius())
+    }
+}
+
+impl FromStr for InsulationRating {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let number = lower
+            .strip_suffix("°c")
+            .or_else(|| lower.strip_suffix('c'))
+            .unwrap_or(&lower);
+        match number {
+            "60" => Ok(InsulationRating::C60),
+            "75" => Ok(InsulationRating::C75),
+            "90" => Ok(InsulationRating::C90),
+            _ => Err(Error::InvalidInsulationRating(s.to_string())),
+        }
+    }
+}
+
+/// Ampacity in amps of `gauge` in `material` with `rating` insulation
+///
+/// Uses NEC 310.16: not more than three current-carrying conductors in a
+/// raceway, cable or earth, at an ambient temperature of 30°C. Sizes that
+/// fall between table rows, such as odd AWG and metric sizes, take the
+/// ampacity of the largest listed size that is not larger than them.
+/// Returns `None` when the table lists no ampacity for the size.
+pub fn ampacity(gauge: &WireGauge, material: Material, rating: InsulationRating) -> Option<f64> {
+    let table = match material {
+        Material::Copper | Material::TinnedCopper => COPPER_AMPACITY,
+        Material::Aluminum | Material::CopperCladAluminum => ALUMINUM_AMPACITY,
+    };
+
+    // Allow for rounding between computed and listed areas
+    let row = table
+        .iter()
+        .rev()
+        .find(|row| row.0.area() <= gauge.area * 1.000001)?;
+    let amps = match rating {
+        InsulationRating::C60 => row.1,
+        InsulationRating::C75 => row.2,
+        InsulationRating::C90 => row.3,
+    };
+
+    (amps > 0).then_some(amps as f64)
+}
+
+/// Ambient temperature correction factor from NEC 310.15(B)(1), relative
+/// to [`AMBIENT_BASE`]
+///
+/// Returns 0 where the insulation is not permitted at that ambient.
+pub fn ambient_correction_factor(ambient: f64, rating: InsulationRating) -> f64 {
+    AMBIENT_CORRECTION
+        .iter()
+        .find(|row| ambient <= row.0)
+        .map_or(0.0, |row| match rating {
+            InsulationRating::C60 => row.1,
+            InsulationRating::C75 => row.2,
+            InsulationRating::C90 => row.3,
+        })
+}
+
+/// Adjustment factor from NEC 310.15(C)(1) for more than three
+/// current-carrying conductors in a raceway or cable
+pub fn adjustment_factor(conductors: u32) -> f64 {
+    match conductors {
+        0..=3 => 1.0,
+        4..=6 => 0.8,
+        7..=9 => 0.7,
+        10..=20 => 0.5,
+        21..=30 => 0.45,
+        31..=40 => 0.4,
+        _ => 0.35,
+    }
+}
+
+// NEC 310.15(B)(1) ambient temperature correction factors
+// Format: (highest ambient °C of the range, 60°C, 75°C, 90°C), 0 where
+// the insulation is not permitted
+const AMBIENT_CORRECTION: &[(f64, f64, f64, f64)] = &[
+    (10.0, 1.29, 1.20, 1.15),
+    (15.0, 1.22, 1.15, 1.12),
+    (20.0, 1.15, 1.11, 1.08),
+    (25.0, 1.08, 1.05, 1.04),
+    (30.0, 1.00, 1.00, 1.00),
+    (35.0, 0.91, 0.94, 0.96),
+    (40.0, 0.82, 0.88, 0.91),
+    (45.0, 0.71, 0.82, 0.87),
+    (50.0, 0.58, 0.75, 0.82),
+    (55.0, 0.41, 0.67, 0.76),
+    (60.0, 0.00, 0.58, 0.71),
+    (65.0, 0.00, 0.47, 0.65),
+    (70.0, 0.00, 0.33, 0.58),
+    (75.0, 0.00, 0.00, 0.50),
+    (80.0, 0.00, 0.00, 0.41),
+    (85.0, 0.00, 0.00, 0.29),
+];
+
+// NEC 310.16 copper conductors
+// Format: (size, 60°C, 75°C, 90°C), 0 where no ampacity is listed
+const COPPER_AMPACITY: &[(WireSize, u32, u32, u32)] = &[
+    (WireSize::Awg(Awg::new(18)), 0, 0, 14),
+    (WireSize::Awg(Awg::new(16)), 0, 0, 18),
+    (WireSize::Awg(Awg::new(14)), 15, 20, 25),
+    (WireSize::Awg(Awg::new(12)), 20, 25, 30),
+    (WireSize::Awg(Awg::new(10)), 30, 35, 40),
+    (WireSize::Awg(Awg::new(8)), 40, 50, 55),
+    (WireSize::Awg(Awg::new(6)), 55, 65, 75),
+    (WireSize::Awg(Awg::new(4)), 70, 85, 95),
+    (WireSize::Awg(Awg::new(3)), 85, 100, 115),
+    (WireSize::Awg(Awg::new(2)), 95, 115, 130),
+    (WireSize::Awg(Awg::new(1)), 110, 130, 145),
+    (WireSize::Awg(Awg::new(0)), 125, 150, 170),
+    (WireSize::Awg(Awg::new(-1)), 145, 175, 195),
+    (WireSize::Awg(Awg::new(-2)), 165, 200, 225),
+    (WireSize::Awg(Awg::new(-3)), 195, 230, 260),
+    (WireSize::Kcmil(250), 215, 255, 290),
+    (WireSize::Kcmil(300), 240, 285, 320),
+    (WireSize::Kcmil(350), 260, 310, 350),
+    (WireSize::Kcmil(400), 280, 335, 380),
+    (WireSize::Kcmil(500), 320, 380, 430),
+    (WireSize::Kcmil(600), 350, 420, 475),
+    (WireSize::Kcmil(700), 385, 460, 520),
+    (WireSize::Kcmil(750), 400, 475, 535),
+    (WireSize::Kcmil(800), 410, 490, 555),
+    (WireSize::Kcmil(900), 435, 520, 585),
+    (WireSize::Kcmil(1000), 455, 545, 615),
+    (WireSize::Kcmil(1250), 495, 590, 665),
+    (WireSize::Kcmil(1500), 525, 625, 705),
+    (WireSize::Kcmil(1750), 545, 650, 735),
+    (WireSize::Kcmil(2000), 555, 665, 750),
+];
+
+// NEC 310.16 aluminum and copper-clad aluminum conductors
+// Format: (size, 60°C, 75°C, 90°C)
+const ALUMINUM_AMPACITY: &[(WireSize, u32, u32, u32)] = &[
+    (WireSize::Awg(Awg::new(12)), 15, 20, 25),
+    (WireSize::Awg(Awg::new(10)), 25, 30, 35),
+    (WireSize::Awg(Awg::new(8)), 35, 40, 45),
+    (WireSize::Awg(Awg::new(6)), 40, 50, 55),
+    (WireSize::Awg(Awg::new(4)), 55, 65, 75),
+    (WireSize::Awg(Awg::new(3)), 65, 75, 85),
+    (WireSize::Awg(Awg::new(2)), 75, 90, 100),
+    (WireSize::Awg(Awg::new(1)), 85, 100, 115),
+    (WireSize::Awg(Awg::new(0)), 100, 120, 135),
+    (WireSize::Awg(Awg::new(-1)), 115, 135, 150),
+    (WireSize::Awg(Awg::new(-2)), 130, 155, 175),
+    (WireSize::Awg(Awg::new(-3)), 150, 180, 205),
+    (WireSize::Kcmil(250), 170, 205, 230),
+    (WireSize::Kcmil(300), 195, 230, 260),
+    (WireSize::Kcmil(350), 210, 250, 280),
+    (WireSize::Kcmil(400), 225, 270, 305),
+    (WireSize::Kcmil(500), 260, 310, 350),
+    (WireSize::Kcmil(600), 285, 340, 385),
+    (WireSize::Kcmil(700), 315, 375, 425),
+    (WireSize::Kcmil(750), 320, 385, 435),
+    (WireSize::Kcmil(800), 330, 395, 445),
+    (WireSize::Kcmil(900), 355, 425, 480),
+    (WireSize::Kcmil(1000), 375, 445, 500),
+    (WireSize::Kcmil(1250), 405, 485, 545),
+    (WireSize::Kcmil(1500), 435, 520, 585),
+    (WireSize::Kcmil(1750), 455, 545, 615),
+    (WireSize::Kcmil(2000), 470, 560, 630),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gauge::gauge;
+    use crate::Circuit;
+
+    #[test]
+    fn looks_up_the_insulation_column() {
+        let copper = |size, rating| ampacity(&gauge(size), Material::Copper, rating);
+        assert_eq!(copper("12", InsulationRating::C60), Some(20.0));
+        assert_eq!(copper("12", InsulationRating::C75), Some(25.0));
+        assert_eq!(copper("12", InsulationRating::C90), Some(30.0));
+        assert_eq!(copper("250kcmil", InsulationRating::C75), Some(255.0));
+        assert_eq!(ampacity(&gauge("12"), Material::Aluminum, InsulationRating::C75), Some(20.0));
+    }
+
+    #[test]
+    fn sizes_between_rows_take_the_smaller_row() {
+        let copper = |size| ampacity(&gauge(size), Material::Copper, InsulationRating::C75);
+        assert_eq!(copper("13"), copper("14"));
+        assert_eq!(copper("5"), copper("6"));
+    }
+
+    #[test]
+    fn unlisted_sizes_have_no_ampacity() {
+        assert_eq!(ampacity(&gauge("16"), Material::Copper, InsulationRating::C75), None);
+        assert_eq!(ampacity(&gauge("16"), Material::Copper, InsulationRating::C90), Some(18.0));
+        assert_eq!(ampacity(&gauge("22"), Material::Copper, InsulationRating::C90), None);
+        assert_eq!(ampacity(&gauge("14"), Material::Aluminum, InsulationRating::C75), None);
+    }
+
+    #[test]
+    fn looks_up_the_ambient_correction_range() {
+        assert_eq!(ambient_correction_factor(30.0, InsulationRating::C75), 1.0);
+        assert_eq!(ambient_correction_factor(40.0, InsulationRating::C75), 0.88);
+        // 31°C falls in the 31-35°C row
+        assert_eq!(ambient_correction_factor(31.0, InsulationRating::C90), 0.96);
+        assert_eq!(ambient_correction_factor(5.0, InsulationRating::C60), 1.29);
+        // Not permitted at all
+        assert_eq!(ambient_correction_factor(60.0, InsulationRating::C60), 0.0);
+        assert_eq!(ambient_correction_factor(90.0, InsulationRating::C90), 0.0);
+    }
+
+    #[test]
+    fn adjusts_for_more_than_three_conductors() {
+        let factors: Vec<f64> = [3, 4, 6, 7, 9, 10, 20, 21, 30, 31, 40, 41]
+            .into_iter()
+            .map(adjustment_factor)
+            .collect();
+        assert_eq!(
+            factors,
+            [1.0, 0.8, 0.8, 0.7, 0.7, 0.5, 0.5, 0.45, 0.45, 0.4, 0.4, 0.35]
+        );
+    }
+
+    #[test]
+    fn derates_for_ambient_and_bundling_together() {
+        // 12 AWG at 90°C is rated 30 A; 40°C ambient (0.91) and six
+        // conductors (0.8) bring it to 21.84 A
+        let circuit = Circuit {
+            insulation_rating: InsulationRating::C90,
+            ambient: 40.0,
+            conductors_in_raceway: 6,
+            ..Circuit::new(120.0, 0.0, 0.0)
+        };
+        let derated = circuit.derated_ampacity(&gauge("12")).unwrap();
+        assert!((derated - 21.84).abs() < 1e-9);
+        assert!(circuit.check_ampacity(&gauge("12"), 21.8).2);
+        assert!(!circuit.check_ampacity(&gauge("12"), 22.0).2);
+    }
+}
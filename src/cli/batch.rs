@@ -0,0 +1,374 @@
+//! `batch` mode: the recommended gauge for each circuit in a CSV file
+
+use std::path::PathBuf;
+
+use clap::Args;
+use prettytable::Table;
+use serde::{Deserialize, Serialize};
+use wgrs::{calculate, recommended, Circuit, DropResult, Error, Standard, WireGauge};
+
+use super::{
+    add_row, ampacity_problem, exit_with_error, format_optional, print_csv, print_json,
+    print_markdown, set_error_format, status, Format, WireArgs,
+};
+
+/// Recommended gauge for every circuit listed in a CSV file
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// CSV file with the columns name, voltage, current, distance and,
+    /// optionally, max_drop and gauge
+    pub file: PathBuf,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+/// A row of the input file
+#[derive(Deserialize)]
+struct BatchCircuit {
+    name: String,
+    voltage: f64,
+    current: f64,
+    distance: f64,
+    #[serde(default)]
+    max_drop: Option<f64>,
+    #[serde(default)]
+    gauge: Option<String>,
+}
+
+/// The recommended gauge for one circuit, and the check of its given gauge
+///
+/// The inputs are `None` for a row of the file that could not be read.
+#[derive(Serialize)]
+pub struct CircuitSummary {
+    name: String,
+    #[serde(skip)]
+    circuit: Option<Circuit>,
+    voltage: Option<f64>,
+    current: Option<f64>,
+    distance: Option<f64>,
+    max_drop: Option<f64>,
+    material: Option<&'static str>,
+    system: Option<&'static str>,
+    gauge: Option<String>,
+    gauge_acceptable: Option<bool>,
+    recommended_gauge: Option<String>,
+    voltage_drop: Option<f64>,
+    drop_percentage: Option<f64>,
+    problems: Vec<String>,
+}
+
+impl CircuitSummary {
+    /// Summary of a row that could not be read, with the reason as its
+    /// problem
+    fn invalid(name: String, reason: &str) -> Self {
+        CircuitSummary {
+            name,
+            circuit: None,
+            voltage: None,
+            current: None,
+            distance: None,
+            max_drop: None,
+            material: None,
+            system: None,
+            gauge: None,
+            gauge_acceptable: None,
+            recommended_gauge: None,
+            voltage_drop: None,
+            drop_percentage: None,
+            problems: vec![format!("Invalid row: {}", reason)],
+        }
+    }
+}
+
+pub fn run(args: &BatchArgs) {
+    set_error_format(args.wire.format);
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(&args.file)
+        .unwrap_or_else(|err| exit_with_error(format!("{}: {}", args.file.display(), err)));
+    let headers = reader
+        .headers()
+        .unwrap_or_else(|err| exit_with_error(format!("{}: {}", args.file.display(), err)))
+        .clone();
+    let gauges = args.wire.gauges();
+
+    // A row that cannot be read is reported in its place, and the others
+    // are still checked
+    let mut summaries = Vec::new();
+    let mut invalid_rows = 0;
+    let name_column = headers.iter().position(|header| header == "name");
+    for (index, record) in reader.records().enumerate() {
+        let name = record
+            .as_ref()
+            .ok()
+            .zip(name_column)
+            .and_then(|(record, column)| record.get(column))
+            .filter(|name| !name.is_empty())
+            .map_or_else(|| format!("Row {}", index + 1), str::to_string);
+        let row = record
+            .map_err(|err| match err.kind() {
+                csv::ErrorKind::UnequalLengths { expected_len, len, .. } => {
+                    format!("{} fields where the header has {}", len, expected_len)
+                }
+                _ => err.to_string(),
+            })
+            .and_then(|record| read_row(&headers, &record, args.wire.standard));
+        match row {
+            Ok((row, gauge)) => {
+                let mut circuit = args.wire.circuit(row.voltage, row.current, row.distance);
+                if let Some(max_drop) = row.max_drop {
+                    circuit.max_drop = max_drop;
+                }
+                summaries.push(summarize(row.name, circuit, gauge, &gauges));
+            }
+            Err(reason) => {
+                invalid_rows += 1;
+                summaries.push(CircuitSummary::invalid(name, &reason));
+            }
+        }
+    }
+
+    let mut parameters = vec![format!("Circuits: {} ({})", summaries.len(), args.file.display())];
+    parameters.extend(args.wire.parameters(&args.wire.circuit(0.0, 0.0, 0.0)));
+    let title = "Batch Voltage Drop Calculator";
+    print_summaries(title, &parameters, &summaries, false, args.wire.format);
+    if invalid_rows > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Read a row of the input file, and its gauge if it gives one
+///
+/// The error names the column that could not be read, when there is one.
+fn read_row(
+    headers: &csv::StringRecord,
+    record: &csv::StringRecord,
+    standard: Standard,
+) -> Result<(BatchCircuit, Option<WireGauge>), String> {
+    let row: BatchCircuit = record.deserialize(Some(headers)).map_err(|err| match err.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => {
+            match err.field().and_then(|field| headers.get(field as usize)) {
+                Some(column) => format!("{}: {}", column, err.kind()),
+                None => err.kind().to_string(),
+            }
+        }
+        _ => err.to_string(),
+    })?;
+    let gauge = match row.gauge {
+        Some(ref size) => Some(
+            standard
+                .select_gauges(&[size])
+                .map_err(|err| match err {
+                    // Without the list of every valid gauge
+                    Error::InvalidGauge { size, .. } => format!("gauge: unknown gauge {}", size),
+                    err => err.to_string(),
+                })?
+                .remove(0),
+        ),
+        None => None,
+    };
+    Ok((row, gauge))
+}
+
+/// Check `circuit` with its given `gauge`, if any, and find the smallest of
+/// `gauges` that passes
+pub fn summarize(
+    name: String,
+    circuit: Circuit,
+    gauge: Option<WireGauge>,
+    gauges: &[WireGauge],
+) -> CircuitSummary {
+    let mut problems = Vec::new();
+    let given = gauge.map(|gauge| calculate(&circuit, &[gauge]).remove(0));
+    if let Some(ref given) = given {
+        problems.extend(gauge_problems(given).into_iter().map(str::to_string));
+    }
+
+    let drops = calculate(&circuit, gauges);
+    let best = recommended(&drops);
+    if best.is_none() {
+        problems.push("No passing gauge".to_string());
+    }
+    CircuitSummary {
+        name,
+        voltage: Some(circuit.voltage),
+        current: Some(circuit.current),
+        distance: Some(circuit.distance),
+        max_drop: Some(circuit.max_drop),
+        material: Some(circuit.material.id()),
+        system: Some(circuit.system.id()),
+        gauge: given.as_ref().map(|result| result.gauge.to_string()),
+        gauge_acceptable: given.as_ref().map(|result| result.acceptable),
+        recommended_gauge: best.map(|result| result.gauge.to_string()),
+        voltage_drop: best.map(|result| result.voltage_drop),
+        drop_percentage: best.map(|result| result.drop_percentage),
+        problems,
+        circuit: Some(circuit),
+    }
+}
+
+/// Print one row per circuit in `format`, with the material and system of
+/// each when `installation` is set
+pub fn print_summaries(
+    title: &str,
+    parameters: &[String],
+    summaries: &[CircuitSummary],
+    installation: bool,
+    format: Format,
+) {
+    if format == Format::Json {
+        return print_json(summaries);
+    }
+
+    let given_gauges = summaries.iter().any(|summary| summary.gauge.is_some());
+    let mut header = vec!["Circuit", "Voltage (V)", "Current (A)", "Distance (ft)", "Max Drop (%)"];
+    if installation {
+        header.extend(["Material", "System"]);
+    }
+    if given_gauges {
+        header.push("Gauge");
+    }
+    header.extend(["Recommended Gauge", "Voltage Drop (V)", "Drop (%)", "Status"]);
+
+    let full_precision = format == Format::Csv;
+    let missing = || format_optional(None, 0, full_precision);
+    let rows: Vec<Vec<String>> = summaries
+        .iter()
+        .map(|summary| {
+            let input = |value: Option<f64>| value.map_or_else(missing, |value| value.to_string());
+            let mut cells = vec![
+                summary.name.clone(),
+                input(summary.voltage),
+                input(summary.current),
+                input(summary.distance),
+                input(summary.max_drop),
+            ];
+            if installation {
+                let circuit = summary.circuit.as_ref();
+                cells.push(circuit.map_or_else(missing, |c| c.material.to_string()));
+                cells.push(circuit.map_or_else(missing, |c| c.system.to_string()));
+            }
+            if given_gauges {
+                cells.push(summary.gauge.clone().unwrap_or_default());
+            }
+            cells.extend([
+                summary.recommended_gauge.clone().unwrap_or_else(missing),
+                format_optional(summary.voltage_drop, 3, full_precision),
+                format_optional(summary.drop_percentage, 2, full_precision),
+                status(&summary.problems),
+            ]);
+            cells
+        })
+        .collect();
+
+    match format {
+        Format::Csv => return print_csv(&header, &rows),
+        Format::Markdown => return print_markdown(&header, &rows),
+        Format::Table | Format::Json => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in &rows {
+        add_row(&mut table, cells);
+    }
+
+    println!("\n=== {} ===\n", title);
+    println!("Input Parameters:");
+    for parameter in parameters {
+        println!("  {}", parameter);
+    }
+    println!();
+
+    table.printstd();
+
+    println!();
+    let invalid: Vec<&str> = summaries
+        .iter()
+        .filter(|summary| summary.circuit.is_none())
+        .map(|summary| summary.name.as_str())
+        .collect();
+    if !invalid.is_empty() {
+        println!("WARNING: {} rows could not be read: {}", invalid.len(), invalid.join(", "));
+    }
+    let failed: Vec<&str> = summaries
+        .iter()
+        .filter(|summary| summary.circuit.is_some() && summary.recommended_gauge.is_none())
+        .map(|summary| summary.name.as_str())
+        .collect();
+    let circuits = summaries.len() - invalid.len();
+    if failed.is_empty() && invalid.is_empty() {
+        println!("Every circuit has a gauge that meets the voltage drop and ampacity requirements.");
+    } else if failed.is_empty() {
+        println!(
+            "Every other circuit has a gauge that meets the voltage drop and ampacity requirements."
+        );
+    } else {
+        println!(
+            "WARNING: No gauge meets the voltage drop and ampacity requirements for {} of {} circuits: {}",
+            failed.len(),
+            circuits,
+            failed.join(", ")
+        );
+    }
+}
+
+/// Failed checks of the gauge given for a circuit
+fn gauge_problems(result: &DropResult) -> Vec<&'static str> {
+    let mut problems = Vec::new();
+    if !result.within_max_drop {
+        problems.push("Gauge has too much drop");
+    }
+    problems.extend(
+        ampacity_problem(result.within_ampacity, result.ampacity).map(|problem| match problem {
+            "Over ampacity" => "Gauge over ampacity",
+            _ => "Gauge has no ampacity rating",
+        }),
+    );
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(row: &[&str]) -> Result<(BatchCircuit, Option<WireGauge>), String> {
+        let headers = csv::StringRecord::from(vec![
+            "name", "voltage", "current", "distance", "max_drop", "gauge",
+        ]);
+        read_row(&headers, &csv::StringRecord::from(row.to_vec()), Standard::Awg)
+    }
+
+    #[test]
+    fn reads_a_row_and_its_gauge() {
+        let (row, gauge) = read(&["Well pump", "240", "12", "250", "2.5", "10"]).unwrap();
+        assert_eq!(row.name, "Well pump");
+        assert_eq!((row.voltage, row.current, row.distance), (240.0, 12.0, 250.0));
+        assert_eq!(row.max_drop, Some(2.5));
+        assert_eq!(gauge.unwrap().to_string(), "10 AWG");
+    }
+
+    #[test]
+    fn optional_columns_may_be_empty() {
+        let (row, gauge) = read(&["Shop", "240", "60", "150", "", ""]).unwrap();
+        assert_eq!(row.max_drop, None);
+        assert!(gauge.is_none());
+    }
+
+    #[test]
+    fn names_the_column_that_cannot_be_read() {
+        let err = read(&["Shop", "240", "sixty", "150", "", ""]).err().unwrap();
+        assert!(err.starts_with("current: "), "{}", err);
+        let err = read(&["Shop", "240", "60", "150", "", "13.7"]).err().unwrap();
+        assert_eq!(err, "gauge: unknown gauge 13.7");
+    }
+
+    #[test]
+    fn an_invalid_row_is_summarized_with_its_reason() {
+        let summary = CircuitSummary::invalid("Shop".to_string(), "current: invalid float literal");
+        assert!(summary.circuit.is_none());
+        assert!(summary.recommended_gauge.is_none());
+        assert_eq!(summary.problems, ["Invalid row: current: invalid float literal"]);
+    }
+}
@@ -0,0 +1,461 @@
+//! Default mode: voltage drop per gauge for a known run
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{solve_load, Circuit, Load, LoadResult, OperatingPoint};
+
+use super::report::Report;
+use super::{
+    add_row, ampacity_problem, exit_with_error, format_number, format_optional, is_derated,
+    parse_positive, print_csv, print_json, print_markdown, set_error_format, status,
+    voltage_parameter, Format, WireArgs, WireInputs,
+};
+
+/// Title of the calculator's output
+const TITLE: &str = "Wire Gauge Voltage Drop Calculator";
+
+/// Voltage drop for a known voltage, current and distance
+#[derive(Args, Debug)]
+pub struct DropArgs {
+    /// Voltage in volts (line-to-line for split-phase and three-phase systems)
+    #[arg(short, long, required = false, required_unless_present = "project")]
+    pub voltage: f64,
+
+    /// Current in amps
+    #[arg(short, long, required_unless_present_any = ["power", "load_resistance", "load_watts", "project"])]
+    pub current: Option<f64>,
+
+    /// Constant-power load in watts, in place of --current
+    #[arg(short, long, value_parser = parse_positive, conflicts_with = "current")]
+    pub power: Option<f64>,
+
+    /// Resistive load in ohms (per phase for three-phase), in place of --current
+    #[arg(long, value_parser = parse_positive, conflicts_with_all = ["current", "power"])]
+    pub load_resistance: Option<f64>,
+
+    /// Resistive load from its rating as watts@volts (e.g. 60@12), in place
+    /// of --current
+    #[arg(long, value_parser = parse_rating, conflicts_with_all = ["current", "power", "load_resistance"])]
+    pub load_watts: Option<(f64, f64)>,
+
+    /// One-way distance in feet
+    #[arg(short, long, required = false, required_unless_present = "project")]
+    pub distance: f64,
+
+    /// Lowest source voltage in volts, e.g. a discharged battery; the drop is
+    /// evaluated at this voltage [default: --voltage]
+    #[arg(long)]
+    pub voltage_min: Option<f64>,
+
+    /// Minimum voltage in volts the load needs; replaces --max-drop as the
+    /// voltage drop check
+    #[arg(long)]
+    pub min_load_voltage: Option<f64>,
+
+    /// Columns to show, in order, separated by commas [default: the
+    /// columns relevant to the load and installation]
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub columns: Option<Vec<Column>>,
+
+    /// Also write a standalone HTML calculation report to this file
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+}
+
+/// A column of the results table
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    /// Wire gauge
+    Gauge,
+    /// Total resistance of the run in ohms
+    Resistance,
+    /// Effective impedance in ohms per 1000 feet
+    Impedance,
+    /// Current drawn in amps
+    Current,
+    /// Voltage drop in volts
+    Drop,
+    /// Voltage drop as a percentage
+    DropPercent,
+    /// Voltage at the load in volts
+    LoadVoltage,
+    /// Power delivered to the load in watts
+    LoadPower,
+    /// Power lost in the conductors in watts
+    Loss,
+    /// Power lost as a percentage of the power sent
+    LossPercent,
+    /// Power lost per foot of conductor in watts
+    LossPerFoot,
+    /// NEC 310.16 ampacity in amps
+    Ampacity,
+    /// Ampacity after derating in amps
+    Derated,
+    /// Pass or fail
+    Status,
+}
+
+impl Column {
+    /// Header text
+    fn header(self) -> &'static str {
+        match self {
+            Column::Gauge => "Wire Gauge",
+            Column::Resistance => "Resistance (Ω)",
+            Column::Impedance => "Z (Ω/1000 ft)",
+            Column::Current => "Current (A)",
+            Column::Drop => "Voltage Drop (V)",
+            Column::DropPercent => "Drop (%)",
+            Column::LoadVoltage => "Load Voltage (V)",
+            Column::LoadPower => "Load Power (W)",
+            Column::Loss => "Power Loss (W)",
+            Column::LossPercent => "Power Loss (%)",
+            Column::LossPerFoot => "Loss (W/ft)",
+            Column::Ampacity => "Ampacity (A)",
+            Column::Derated => "Derated (A)",
+            Column::Status => "Status",
+        }
+    }
+
+    /// Cell text for `result`, with numbers rounded for display or in full
+    fn cell(self, circuit: &Circuit, result: &LoadResult, full_precision: bool) -> String {
+        match self {
+            Column::Gauge => return result.gauge.to_string(),
+            Column::Resistance => return format_number(result.total_resistance, 4, full_precision),
+            Column::Status => return status(&load_problems(circuit, result)),
+            // Ampacity depends on the gauge and installation alone
+            Column::Ampacity => {
+                return format_optional(circuit.ampacity(&result.gauge), 0, full_precision)
+            }
+            Column::Derated => {
+                return format_optional(circuit.derated_ampacity(&result.gauge), 1, full_precision)
+            }
+            _ => {}
+        }
+
+        // The rest are meaningless without an operating point
+        let Some(OperatingPoint {
+            current,
+            load_power,
+            ref drop,
+        }) = result.operating_point
+        else {
+            return format_optional(None, 0, full_precision);
+        };
+        match self {
+            Column::Impedance => format_number(drop.impedance, 4, full_precision),
+            Column::Current => format_number(current, 2, full_precision),
+            Column::Drop => format_number(drop.voltage_drop, 3, full_precision),
+            Column::DropPercent => format_number(drop.drop_percentage, 2, full_precision),
+            Column::LoadVoltage => format_number(drop.load_voltage, 3, full_precision),
+            Column::LoadPower => format_number(load_power, 1, full_precision),
+            Column::Loss => format_number(drop.power_loss, 2, full_precision),
+            Column::LossPercent => format_number(drop.power_loss_percentage, 2, full_precision),
+            Column::LossPerFoot => format_number(drop.loss_per_foot, 4, full_precision),
+            Column::Gauge
+            | Column::Resistance
+            | Column::Status
+            | Column::Ampacity
+            | Column::Derated => unreachable!(),
+        }
+    }
+}
+
+impl DropArgs {
+    /// The load described by `--current`, `--power`, `--load-resistance`
+    /// or `--load-watts`
+    fn load(&self) -> Load {
+        if let Some(current) = self.current {
+            Load::Current(current)
+        } else if let Some(power) = self.power {
+            Load::Power(power)
+        } else if let Some(resistance) = self.load_resistance {
+            Load::Resistance(resistance)
+        } else if let Some((watts, volts)) = self.load_watts {
+            Load::from_rating(watts, volts)
+        } else {
+            unreachable!("clap requires one of the load arguments")
+        }
+    }
+}
+
+/// Parse a `watts@volts` load rating
+fn parse_rating(s: &str) -> Result<(f64, f64), String> {
+    let invalid = || format!("'{}' is not a rating like 60@12 (watts@volts)", s);
+    let (watts, volts) = s.split_once('@').ok_or_else(invalid)?;
+    let watts: f64 = watts.trim().trim_end_matches(['W', 'w']).parse().map_err(|_| invalid())?;
+    let volts: f64 = volts.trim().trim_end_matches(['V', 'v']).parse().map_err(|_| invalid())?;
+    if watts <= 0.0 || volts <= 0.0 {
+        return Err(invalid());
+    }
+    Ok((watts, volts))
+}
+
+pub fn run(args: &DropArgs, wire: &WireArgs) {
+    set_error_format(wire.format);
+    let load = args.load();
+    let mut circuit = wire.circuit(args.voltage, args.current.unwrap_or(0.0), args.distance);
+    if args.voltage_min.is_some_and(|voltage_min| voltage_min > args.voltage) {
+        exit_with_error("--voltage-min cannot be greater than --voltage");
+    }
+    circuit.voltage_min = args.voltage_min;
+    circuit.min_load_voltage = args.min_load_voltage;
+
+    let gauges = wire.gauges();
+    let results = solve_load(&circuit, load, &gauges);
+    let derated = is_derated(&circuit);
+    let fixed_current = matches!(load, Load::Current(_));
+    let resistive = matches!(load, Load::Resistance(_));
+    let show_load_voltage =
+        !fixed_current || circuit.voltage_min.is_some() || circuit.min_load_voltage.is_some();
+
+    let columns = args.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![Column::Gauge, Column::Resistance];
+        if circuit.ac.is_some() {
+            columns.push(Column::Impedance);
+        }
+        if !fixed_current {
+            columns.push(Column::Current);
+        }
+        columns.extend([Column::Drop, Column::DropPercent]);
+        if show_load_voltage {
+            columns.push(Column::LoadVoltage);
+        }
+        if resistive {
+            columns.extend([Column::LoadPower, Column::Loss]);
+        }
+        columns.push(Column::Ampacity);
+        if derated {
+            columns.push(Column::Derated);
+        }
+        columns.push(Column::Status);
+        columns
+    });
+    let show_loss = columns
+        .iter()
+        .any(|column| matches!(column, Column::Loss | Column::LossPercent | Column::LossPerFoot));
+
+    let header: Vec<&str> = columns.iter().map(|column| column.header()).collect();
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        results
+            .iter()
+            .map(|result| {
+                columns
+                    .iter()
+                    .map(|column| column.cell(&circuit, result, full_precision))
+                    .collect()
+            })
+            .collect()
+    };
+
+    let mut parameters = vec![voltage_parameter("Voltage", &circuit)];
+    if let Some(voltage_min) = circuit.voltage_min {
+        parameters.push(format!("Minimum Voltage: {} V", voltage_min));
+    }
+    parameters.push(match load {
+        Load::Current(current) => format!("Current: {} A", current),
+        _ => match args.load_watts {
+            Some((watts, volts)) => format!("Load: {} ({} W at {} V)", load, watts, volts),
+            None => format!("Load: {}", load),
+        },
+    });
+    parameters.push(format!("Distance: {} ft (one way)", circuit.distance));
+    parameters.extend(wire.parameters(&circuit));
+
+    let best = results
+        .iter()
+        .find(|result| result.acceptable())
+        .and_then(|result| result.operating_point.as_ref());
+    let mut recommendation = Vec::new();
+    if let Some(best) = best {
+        let drop = &best.drop;
+        recommendation.push(format!("Recommended gauge: {}", drop.gauge));
+        if !fixed_current {
+            recommendation.push(format!("  Current: {:.2} A", best.current));
+        }
+        recommendation.push(format!(
+            "  Voltage drop: {:.3} V ({:.2}%)",
+            drop.voltage_drop, drop.drop_percentage
+        ));
+        if show_load_voltage {
+            recommendation.push(format!("  Load voltage: {:.3} V", drop.load_voltage));
+        }
+        if resistive {
+            recommendation.push(format!("  Load power: {:.1} W", best.load_power));
+        }
+        if resistive || show_loss {
+            recommendation.push(format!(
+                "  Power loss: {:.2} W ({:.2}%, {:.4} W/ft)",
+                drop.power_loss, drop.power_loss_percentage, drop.loss_per_foot
+            ));
+        }
+        if let Some(amps) = drop.derated_ampacity {
+            recommendation.push(format!("  Ampacity: {:.1} A at {}", amps, circuit.insulation_rating));
+        }
+    } else {
+        recommendation
+            .push("WARNING: No gauge meets both the voltage drop and ampacity requirements!".to_string());
+    }
+
+    if let Some(ref path) = args.report {
+        let report = Report {
+            title: TITLE,
+            circuit: &circuit,
+            load,
+            standard: wire.standard,
+            parameters: &parameters,
+            header: &header,
+            rows: &rows(false),
+            passed: &results.iter().map(LoadResult::acceptable).collect::<Vec<_>>(),
+            recommendation: &recommendation,
+        };
+        report
+            .write(path)
+            .unwrap_or_else(|err| exit_with_error(format!("{}: {}", path.display(), err)));
+    }
+
+    match wire.format {
+        Format::Json => return print_report(wire, &circuit, load, &results),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    // Create results table
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== {} ===\n", TITLE);
+    println!("Input Parameters:");
+    for parameter in &parameters {
+        println!("  {}", parameter);
+    }
+    println!();
+
+    table.printstd();
+
+    println!();
+    for line in &recommendation {
+        println!("{}", line);
+    }
+}
+
+/// Failed checks of a result, for the status column
+fn load_problems(circuit: &Circuit, result: &LoadResult) -> Vec<&'static str> {
+    let Some(ref point) = result.operating_point else {
+        return vec!["Voltage collapse"];
+    };
+
+    let drop = &point.drop;
+    let mut problems = Vec::new();
+    if !drop.within_max_drop {
+        problems.push(if circuit.min_load_voltage.is_some() {
+            "Load voltage too low"
+        } else {
+            "Too much drop"
+        });
+    }
+    problems.extend(ampacity_problem(drop.within_ampacity, drop.ampacity));
+    problems
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    rows: Vec<JsonRow>,
+    recommended_gauge: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    voltage: f64,
+    voltage_min: Option<f64>,
+    load: LoadInput,
+    distance: f64,
+    min_load_voltage: Option<f64>,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LoadInput {
+    Current(f64),
+    Power(f64),
+    Resistance(f64),
+}
+
+/// A gauge's row; the operating point values are `null` on voltage collapse
+#[derive(Serialize)]
+struct JsonRow {
+    gauge: String,
+    resistance: f64,
+    impedance: Option<f64>,
+    current: Option<f64>,
+    voltage_drop: Option<f64>,
+    drop_percentage: Option<f64>,
+    load_voltage: Option<f64>,
+    load_power: Option<f64>,
+    power_loss: Option<f64>,
+    power_loss_percentage: Option<f64>,
+    loss_per_foot: Option<f64>,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    acceptable: bool,
+    problems: Vec<&'static str>,
+}
+
+impl JsonRow {
+    fn new(circuit: &Circuit, result: &LoadResult) -> Self {
+        let point = result.operating_point.as_ref();
+        let at_point = |value: fn(&OperatingPoint) -> f64| point.map(value);
+        let ampacity = circuit.ampacity(&result.gauge);
+        JsonRow {
+            gauge: result.gauge.to_string(),
+            resistance: result.total_resistance,
+            impedance: at_point(|point| point.drop.impedance),
+            current: at_point(|point| point.current),
+            voltage_drop: at_point(|point| point.drop.voltage_drop),
+            drop_percentage: at_point(|point| point.drop.drop_percentage),
+            load_voltage: at_point(|point| point.drop.load_voltage),
+            load_power: at_point(|point| point.load_power),
+            power_loss: at_point(|point| point.drop.power_loss),
+            power_loss_percentage: at_point(|point| point.drop.power_loss_percentage),
+            loss_per_foot: at_point(|point| point.drop.loss_per_foot),
+            ampacity,
+            derated_ampacity: circuit.derated_ampacity(&result.gauge),
+            acceptable: result.acceptable(),
+            problems: load_problems(circuit, result),
+        }
+    }
+}
+
+/// Print the inputs, every gauge and the recommendation as one JSON document
+fn print_report(wire: &WireArgs, circuit: &Circuit, load: Load, results: &[LoadResult]) {
+    let report = JsonReport {
+        inputs: Inputs {
+            voltage: circuit.voltage,
+            voltage_min: circuit.voltage_min,
+            load: match load {
+                Load::Current(current) => LoadInput::Current(current),
+                Load::Power(power) => LoadInput::Power(power),
+                Load::Resistance(resistance) => LoadInput::Resistance(resistance),
+            },
+            distance: circuit.distance,
+            min_load_voltage: circuit.min_load_voltage,
+            wire: wire.inputs(circuit),
+        },
+        rows: results.iter().map(|result| JsonRow::new(circuit, result)).collect(),
+        recommended_gauge: results
+            .iter()
+            .find(|result| result.acceptable())
+            .map(|result| result.gauge.to_string()),
+    };
+    print_json(&report);
+}
@@ -0,0 +1,147 @@
+//! `max-current` mode: largest load per gauge for an existing run
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{max_current, Circuit, CurrentLimit, CurrentResult};
+
+use super::{
+    add_row, format_number, format_optional, is_derated, parse_positive, print_csv, print_json,
+    print_markdown, print_voltage, set_error_format, Format, WireArgs, WireInputs,
+};
+
+/// Largest current per gauge for a known voltage and distance
+#[derive(Args, Debug)]
+pub struct MaxCurrentArgs {
+    /// Voltage in volts (line-to-line for split-phase and three-phase systems)
+    #[arg(short, long)]
+    pub voltage: f64,
+
+    /// One-way distance in feet
+    #[arg(short, long, value_parser = parse_positive)]
+    pub distance: f64,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &MaxCurrentArgs) {
+    set_error_format(args.wire.format);
+    let circuit = args.wire.circuit(args.voltage, 0.0, args.distance);
+    let gauges = args.wire.gauges();
+    let results = max_current(&circuit, &gauges);
+    let derated = is_derated(&circuit);
+
+    let mut header = vec![
+        "Wire Gauge",
+        if circuit.ac.is_some() {
+            "Z (Ω/1000 ft)"
+        } else {
+            "Resistance (Ω/1000 ft)"
+        },
+        "Drop Limit (A)",
+        "Ampacity (A)",
+    ];
+    if derated {
+        header.push("Derated (A)");
+    }
+    header.extend(["Max Current (A)", "Limited By"]);
+
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        results
+            .iter()
+            .map(|result| {
+                let mut cells = vec![
+                    result.gauge.to_string(),
+                    format_number(result.impedance, 4, full_precision),
+                    format_number(result.drop_limited_current, 1, full_precision),
+                    format_optional(result.ampacity, 0, full_precision),
+                ];
+                if derated {
+                    cells.push(format_optional(result.derated_ampacity, 1, full_precision));
+                }
+                cells.push(format_number(result.max_current, 1, full_precision));
+                cells.push(if result.ampacity.is_some() {
+                    result.limit.to_string()
+                } else {
+                    format!("{} (no ampacity rating)", result.limit)
+                });
+                cells
+            })
+            .collect()
+    };
+
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &results),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== Wire Gauge Maximum Current Calculator ===\n");
+    println!("Input Parameters:");
+    print_voltage("Voltage", &circuit);
+    println!("  Distance: {} ft (one way)", circuit.distance);
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    table.printstd();
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    rows: Vec<JsonRow>,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    voltage: f64,
+    distance: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+struct JsonRow {
+    gauge: String,
+    impedance: f64,
+    drop_limited_current: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    max_current: f64,
+    limit: &'static str,
+}
+
+/// Print the inputs and every gauge as one JSON document
+fn print_report(args: &MaxCurrentArgs, circuit: &Circuit, results: &[CurrentResult]) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            voltage: circuit.voltage,
+            distance: circuit.distance,
+            wire: args.wire.inputs(circuit),
+        },
+        rows: results
+            .iter()
+            .map(|result| JsonRow {
+                gauge: result.gauge.to_string(),
+                impedance: result.impedance,
+                drop_limited_current: result.drop_limited_current,
+                ampacity: result.ampacity,
+                derated_ampacity: result.derated_ampacity,
+                max_current: result.max_current,
+                limit: match result.limit {
+                    CurrentLimit::VoltageDrop => "voltage_drop",
+                    CurrentLimit::Ampacity => "ampacity",
+                },
+            })
+            .collect(),
+    });
+}
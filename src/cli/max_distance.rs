@@ -0,0 +1,146 @@
+//! `max-distance` mode: longest run per gauge within the drop limit
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{max_distance, Circuit, DistanceResult};
+
+use super::{
+    add_row, check_status, format_number, format_optional, is_derated, parse_positive, print_csv,
+    print_json, print_markdown, print_voltage, set_error_format, Format, WireArgs, WireInputs,
+};
+
+/// Longest one-way distance per gauge for a known voltage and current
+#[derive(Args, Debug)]
+pub struct MaxDistanceArgs {
+    /// Voltage in volts (line-to-line for split-phase and three-phase systems)
+    #[arg(short, long)]
+    pub voltage: f64,
+
+    /// Current in amps
+    #[arg(short, long, value_parser = parse_positive)]
+    pub current: f64,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &MaxDistanceArgs) {
+    set_error_format(args.wire.format);
+    let circuit = args.wire.circuit(args.voltage, args.current, 0.0);
+    let gauges = args.wire.gauges();
+    let results = max_distance(&circuit, &gauges);
+    let derated = is_derated(&circuit);
+
+    let mut header = vec![
+        "Wire Gauge",
+        if circuit.ac.is_some() {
+            "Z (Ω/1000 ft)"
+        } else {
+            "Resistance (Ω/1000 ft)"
+        },
+        "Max Distance (ft)",
+        "Ampacity (A)",
+    ];
+    if derated {
+        header.push("Derated (A)");
+    }
+    header.push("Status");
+
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        results
+            .iter()
+            .map(|result| {
+                let mut cells = vec![
+                    result.gauge.to_string(),
+                    format_number(result.impedance, 4, full_precision),
+                    format_number(result.max_distance, 1, full_precision),
+                    format_optional(result.ampacity, 0, full_precision),
+                ];
+                if derated {
+                    cells.push(format_optional(result.derated_ampacity, 1, full_precision));
+                }
+                cells.push(check_status(true, result.within_ampacity, result.ampacity));
+                cells
+            })
+            .collect()
+    };
+
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &results),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== Wire Gauge Maximum Distance Calculator ===\n");
+    println!("Input Parameters:");
+    print_voltage("Voltage", &circuit);
+    println!("  Current: {} A", circuit.current);
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    table.printstd();
+
+    println!();
+    println!(
+        "Distances are one way, at a {}% maximum drop.",
+        circuit.max_drop
+    );
+    if !results.iter().any(|result| result.within_ampacity) {
+        println!("WARNING: No gauge can carry {} A!", circuit.current);
+    }
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    rows: Vec<JsonRow>,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    voltage: f64,
+    current: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+struct JsonRow {
+    gauge: String,
+    impedance: f64,
+    max_distance: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    within_ampacity: bool,
+}
+
+/// Print the inputs and every gauge as one JSON document
+fn print_report(args: &MaxDistanceArgs, circuit: &Circuit, results: &[DistanceResult]) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            voltage: circuit.voltage,
+            current: circuit.current,
+            wire: args.wire.inputs(circuit),
+        },
+        rows: results
+            .iter()
+            .map(|result| JsonRow {
+                gauge: result.gauge.to_string(),
+                impedance: result.impedance,
+                max_distance: result.max_distance,
+                ampacity: result.ampacity,
+                derated_ampacity: result.derated_ampacity,
+                within_ampacity: result.within_ampacity,
+            })
+            .collect(),
+    });
+}
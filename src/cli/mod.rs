@@ -0,0 +1,488 @@
+//! Command-line front end over the `wgrs` library
+
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::OnceLock;
+
+use clap::{ArgMatches, Args, ValueEnum};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+use wgrs::{AcParameters, Circuit, Conduit, InsulationRating, Material, Standard, System, WireGauge};
+
+pub mod batch;
+pub mod drop;
+pub mod max_current;
+pub mod max_distance;
+pub mod network;
+pub mod project;
+pub mod report;
+pub mod segments;
+pub mod source_voltage;
+pub mod taps;
+
+/// How results are written to standard output
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable tables
+    #[default]
+    Table,
+    /// A single JSON document
+    Json,
+    /// The results table as CSV, with numbers in full precision
+    Csv,
+    /// The results table as a Markdown table
+    Markdown,
+}
+
+/// Output format that `exit_with_error` reports errors in
+static ERROR_FORMAT: OnceLock<Format> = OnceLock::new();
+
+/// Report later errors in `format` rather than as plain text
+pub fn set_error_format(format: Format) {
+    let _ = ERROR_FORMAT.set(format);
+}
+
+/// Parse the command line, reporting argument errors as JSON when the
+/// arguments ask for `--format json`
+///
+/// The matches are returned too, for telling the options given on the
+/// command line from their defaults.
+pub fn parse_args<P: clap::Parser>() -> (P, ArgMatches) {
+    let parsed = P::command().try_get_matches().and_then(|matches| {
+        let args = P::from_arg_matches(&matches).map_err(|err| err.format(&mut P::command()))?;
+        Ok((args, matches))
+    });
+    parsed.unwrap_or_else(|err| {
+        let help = matches!(
+            err.kind(),
+            clap::error::ErrorKind::DisplayHelp
+                | clap::error::ErrorKind::DisplayVersion
+                | clap::error::ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        if help || !json_requested(std::env::args().skip(1)) {
+            err.exit();
+        }
+        set_error_format(Format::Json);
+        // Keep clap's message, without the usage and help hint after it
+        let text = err.to_string();
+        let message = text.split("\n\n").next().unwrap_or_default();
+        let message = message.strip_prefix("error: ").unwrap_or(message);
+        exit_with_error(message.lines().map(str::trim).collect::<Vec<_>>().join(" "))
+    })
+}
+
+/// Whether `args` include `--format json`
+fn json_requested(args: impl Iterator<Item = String>) -> bool {
+    let mut previous = String::new();
+    for arg in args {
+        if arg == "--format=json" || (previous == "--format" && arg == "json") {
+            return true;
+        }
+        previous = arg;
+    }
+    false
+}
+
+/// Parse a number that must be greater than zero
+pub fn parse_positive(s: &str) -> Result<f64, String> {
+    match s.trim().parse::<f64>() {
+        Ok(value) if value > 0.0 => Ok(value),
+        Ok(_) => Err("must be greater than 0".to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Conductor and installation options shared by every mode
+#[derive(Args, Debug)]
+pub struct WireArgs {
+    /// Maximum acceptable voltage drop percentage (default: 3%)
+    #[arg(short = 'm', long, default_value = "3.0")]
+    pub max_drop: f64,
+
+    /// Conductor material (copper, aluminum, copper-clad-aluminum, tinned-copper)
+    #[arg(long, default_value = "copper")]
+    pub material: Material,
+
+    /// Conductor temperature in °C
+    #[arg(short, long, default_value = "75.0", allow_negative_numbers = true)]
+    pub temperature: f64,
+
+    /// Insulation temperature rating for the NEC 310.16 ampacity check (60, 75, 90)
+    #[arg(long, default_value = "75")]
+    pub insulation_rating: InsulationRating,
+
+    /// Ambient temperature in °C for the ampacity correction
+    #[arg(long, default_value = "30", allow_negative_numbers = true)]
+    pub ambient: f64,
+
+    /// Number of current-carrying conductors in the raceway or cable
+    #[arg(long, default_value = "3")]
+    pub conductors_in_raceway: u32,
+
+    /// Electrical system (dc, single-phase-2-wire, split-phase-120/240,
+    /// three-phase-3-wire, three-phase-4-wire) [default: dc, or
+    /// single-phase-2-wire with --ac]
+    #[arg(long)]
+    pub system: Option<System>,
+
+    /// Use the AC effective impedance method (NEC Chapter 9 Table 9)
+    #[arg(long)]
+    pub ac: bool,
+
+    /// Load power factor for AC circuits
+    #[arg(long, default_value = "0.85", requires = "ac")]
+    pub power_factor: f64,
+
+    /// Conduit type for AC reactance (pvc, aluminum, steel)
+    #[arg(long, default_value = "pvc", requires = "ac")]
+    pub conduit: Conduit,
+
+    /// Wire sizing standard (awg, metric)
+    #[arg(long, default_value = "awg")]
+    pub standard: Standard,
+
+    /// Wire gauges to show (comma-separated, e.g., 10,12,14, 1/0,00 or 250kcmil,500MCM, or 1.5,2.5 for metric)
+    /// [default: every size but the odd AWG sizes from 27 to 5]
+    #[arg(long, value_delimiter = ',')]
+    pub gauges: Option<Vec<String>>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+}
+
+/// The conductor and installation inputs of a `--format json` document
+#[derive(Serialize)]
+pub struct WireInputs {
+    max_drop: f64,
+    material: &'static str,
+    temperature: f64,
+    insulation_rating: u32,
+    ambient: f64,
+    conductors_in_raceway: u32,
+    derating_factor: f64,
+    system: &'static str,
+    ac: Option<AcInput>,
+    standard: &'static str,
+    gauges: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct AcInput {
+    power_factor: f64,
+    conduit: &'static str,
+}
+
+impl WireArgs {
+    /// Build the circuit described by these options, exiting on invalid
+    /// combinations
+    pub fn circuit(&self, voltage: f64, current: f64, distance: f64) -> Circuit {
+        let system = self.system.unwrap_or(if self.ac {
+            System::SinglePhase2Wire
+        } else {
+            System::Dc
+        });
+        if self.ac && !system.is_ac() {
+            exit_with_error("--ac cannot be used with a DC system");
+        }
+        if self.ac && !(self.power_factor > 0.0 && self.power_factor <= 1.0) {
+            exit_with_error("Power factor must be greater than 0 and at most 1");
+        }
+
+        Circuit {
+            voltage,
+            current,
+            distance,
+            max_drop: self.max_drop,
+            voltage_min: None,
+            min_load_voltage: None,
+            material: self.material,
+            temperature: self.temperature,
+            insulation_rating: self.insulation_rating,
+            ambient: self.ambient,
+            conductors_in_raceway: self.conductors_in_raceway,
+            system,
+            ac: self.ac.then_some(AcParameters {
+                power_factor: self.power_factor,
+                conduit: self.conduit,
+            }),
+        }
+    }
+
+    /// The gauges to evaluate, exiting if `--gauges` names an unknown size
+    pub fn gauges(&self) -> Vec<WireGauge> {
+        match self.gauges {
+            Some(ref requested_gauges) => self
+                .standard
+                .select_gauges(requested_gauges)
+                .unwrap_or_else(|err| exit_with_error(err)),
+            None => self.standard.default_gauges().to_vec(),
+        }
+    }
+
+    /// The conductor and installation inputs of `circuit`, for JSON output
+    pub fn inputs(&self, circuit: &Circuit) -> WireInputs {
+        WireInputs {
+            max_drop: circuit.max_drop,
+            material: circuit.material.id(),
+            temperature: circuit.temperature,
+            insulation_rating: circuit.insulation_rating.celsius(),
+            ambient: circuit.ambient,
+            conductors_in_raceway: circuit.conductors_in_raceway,
+            derating_factor: circuit.derating_factor(),
+            system: circuit.system.id(),
+            ac: circuit.ac.map(|ac| AcInput {
+                power_factor: ac.power_factor,
+                conduit: ac.conduit.id(),
+            }),
+            standard: self.standard.id(),
+            gauges: self.gauges.clone(),
+        }
+    }
+
+    /// Print the conductor and installation lines of the input parameters
+    pub fn print_parameters(&self, circuit: &Circuit) {
+        for parameter in self.parameters(circuit) {
+            println!("  {}", parameter);
+        }
+    }
+
+    /// The conductor and installation lines of the input parameters
+    pub fn parameters(&self, circuit: &Circuit) -> Vec<String> {
+        let mut parameters = vec![match circuit.min_load_voltage {
+            Some(min_load_voltage) => format!("Minimum Load Voltage: {} V", min_load_voltage),
+            None => format!("Max Acceptable Drop: {}%", circuit.max_drop),
+        }];
+        parameters.push(format!("Material: {}", circuit.material));
+        parameters.push(format!("Conductor Temperature: {}°C", circuit.temperature));
+        parameters.push(format!("Insulation Rating: {}", circuit.insulation_rating));
+        if is_derated(circuit) {
+            parameters.push(format!(
+                "Ampacity Derating: {}°C ambient, {} conductors in raceway (factor {:.2})",
+                circuit.ambient,
+                circuit.conductors_in_raceway,
+                circuit.derating_factor()
+            ));
+        }
+        parameters.push(format!("System: {}", circuit.system));
+        if let Some(ac) = circuit.ac {
+            parameters.push(format!("AC: power factor {}, {} conduit", ac.power_factor, ac.conduit));
+        }
+        parameters.push(format!("Standard: {}", self.standard));
+        if let Some(ref gauges) = self.gauges {
+            parameters.push(format!("Filtered Gauges: [{}]", gauges.join(", ")));
+        }
+        parameters
+    }
+}
+
+/// Print the circuit voltage line of the input parameters
+pub fn print_voltage(label: &str, circuit: &Circuit) {
+    println!("  {}", voltage_parameter(label, circuit));
+}
+
+/// The circuit voltage line of the input parameters
+pub fn voltage_parameter(label: &str, circuit: &Circuit) -> String {
+    match circuit.system.line_to_neutral(circuit.voltage) {
+        Some(line_to_neutral) => format!(
+            "{}: {} V line-to-line ({:.0} V line-to-neutral)",
+            label, circuit.voltage, line_to_neutral
+        ),
+        None => format!("{}: {} V", label, circuit.voltage),
+    }
+}
+
+/// Whether the circuit's ampacity is derated for ambient or bundling
+pub fn is_derated(circuit: &Circuit) -> bool {
+    circuit.derating_factor() != 1.0
+}
+
+/// Table cell text for a number, rounded for display or in full precision
+pub fn format_number(value: f64, precision: usize, full_precision: bool) -> String {
+    if full_precision {
+        value.to_string()
+    } else {
+        format!("{:.*}", precision, value)
+    }
+}
+
+/// Table cell text for an optional number, such as an ampacity: `-` when
+/// there is none, or an empty cell in full precision
+pub fn format_optional(value: Option<f64>, precision: usize, full_precision: bool) -> String {
+    match value {
+        Some(value) => format_number(value, precision, full_precision),
+        None if full_precision => String::new(),
+        None => "-".to_string(),
+    }
+}
+
+/// Status column text from a list of failed checks
+pub fn status<S: AsRef<str>>(problems: &[S]) -> String {
+    if problems.is_empty() {
+        "✓ OK".to_string()
+    } else {
+        let problems: Vec<&str> = problems.iter().map(AsRef::as_ref).collect();
+        format!("✗ {}", problems.join(", "))
+    }
+}
+
+/// Failed checks of a gauge checked against the maximum drop and its
+/// ampacity
+pub fn check_problems(
+    within_max_drop: bool,
+    within_ampacity: bool,
+    ampacity: Option<f64>,
+) -> Vec<&'static str> {
+    let mut problems = Vec::new();
+    if !within_max_drop {
+        problems.push("Too much drop");
+    }
+    problems.extend(ampacity_problem(within_ampacity, ampacity));
+    problems
+}
+
+/// Status column text for a gauge checked against the maximum drop and its
+/// ampacity
+pub fn check_status(within_max_drop: bool, within_ampacity: bool, ampacity: Option<f64>) -> String {
+    status(&check_problems(within_max_drop, within_ampacity, ampacity))
+}
+
+/// Ampacity problem for the status column, if the gauge fails the check
+pub fn ampacity_problem(within_ampacity: bool, ampacity: Option<f64>) -> Option<&'static str> {
+    match (within_ampacity, ampacity) {
+        (true, _) => None,
+        (false, Some(_)) => Some("Over ampacity"),
+        (false, None) => Some("No ampacity rating"),
+    }
+}
+
+/// Add a row of cells to `table`
+pub fn add_row<S: AsRef<str>>(table: &mut Table, cells: &[S]) {
+    table.add_row(Row::new(cells.iter().map(|cell| Cell::new(cell.as_ref())).collect()));
+}
+
+/// Print `header` and `rows` as CSV
+pub fn print_csv<S: AsRef<str>>(header: &[&str], rows: &[Vec<S>]) {
+    write_csv(std::io::stdout(), header, rows).unwrap_or_else(|err| exit_with_error(err));
+}
+
+/// Write `header` and `rows` as CSV to `out`
+fn write_csv<W: Write, S: AsRef<str>>(out: W, header: &[&str], rows: &[Vec<S>]) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(header)?;
+    for row in rows {
+        writer.write_record(row.iter().map(AsRef::as_ref))?;
+    }
+    Ok(writer.flush()?)
+}
+
+/// Print `header` and `rows` as a Markdown table
+pub fn print_markdown<S: AsRef<str>>(header: &[&str], rows: &[Vec<S>]) {
+    print!("{}", markdown_table(header, rows));
+}
+
+/// `header` and `rows` as the lines of a Markdown table, with pipes in the
+/// cells escaped
+fn markdown_table<S: AsRef<str>>(header: &[&str], rows: &[Vec<S>]) -> String {
+    let line = |cells: Vec<&str>| {
+        let cells: Vec<String> = cells.iter().map(|cell| cell.replace('|', "\\|")).collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+    let mut table = line(header.to_vec());
+    table.push_str(&line(header.iter().map(|_| "---").collect()));
+    for row in rows {
+        table.push_str(&line(row.iter().map(AsRef::as_ref).collect()));
+    }
+    table
+}
+
+/// Print `document` as pretty-printed JSON
+pub fn print_json<T: Serialize + ?Sized>(document: &T) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(document).unwrap_or_else(|err| exit_with_error(err))
+    );
+}
+
+/// Print an error and exit with a failure status
+///
+/// With JSON output the error is written to standard output as
+/// `{"error": "..."}` so scripts always get a document to parse.
+pub fn exit_with_error(err: impl Display) -> ! {
+    let format = ERROR_FORMAT.get().copied().unwrap_or_default();
+    let message = error_message(err, format);
+    match format {
+        Format::Table | Format::Csv | Format::Markdown => eprintln!("{}", message),
+        Format::Json => println!("{}", message),
+    }
+    std::process::exit(1);
+}
+
+/// `err` as `exit_with_error` reports it in `format`
+fn error_message(err: impl Display, format: Format) -> String {
+    match format {
+        Format::Table | Format::Csv | Format::Markdown => format!("Error: {}", err),
+        Format::Json => serde_json::json!({ "error": err.to_string() }).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn detects_a_request_for_json() {
+        assert!(json_requested(args(&["-v", "12", "--format", "json"])));
+        assert!(json_requested(args(&["--format=json", "-v", "12"])));
+        assert!(json_requested(args(&["batch", "--format", "json", "circuits.csv"])));
+        assert!(!json_requested(args(&["--format", "csv", "json"])));
+        assert!(!json_requested(args(&["--format=csv"])));
+        assert!(!json_requested(args(&["json"])));
+    }
+
+    #[test]
+    fn reports_errors_as_text_or_a_json_document() {
+        let err = "unknown material \"tin\"";
+        assert_eq!(error_message(err, Format::Table), "Error: unknown material \"tin\"");
+        assert_eq!(error_message(err, Format::Csv), "Error: unknown material \"tin\"");
+        let json: serde_json::Value =
+            serde_json::from_str(&error_message(err, Format::Json)).unwrap();
+        assert_eq!(json, serde_json::json!({ "error": "unknown material \"tin\"" }));
+    }
+
+    #[test]
+    fn formats_numbers_rounded_or_in_full() {
+        assert_eq!(format_number(1.23456, 2, false), "1.23");
+        assert_eq!(format_number(1.23456, 2, true), "1.23456");
+        assert_eq!(format_optional(Some(25.0), 0, false), "25");
+        assert_eq!(format_optional(None, 0, false), "-");
+        assert_eq!(format_optional(None, 0, true), "");
+    }
+
+    #[test]
+    fn writes_csv_with_quoting() {
+        let rows = vec![
+            vec!["12 AWG".to_string(), format_number(0.30904, 3, true)],
+            vec!["Kitchen, north".to_string(), format_optional(None, 3, true)],
+        ];
+        let mut out = Vec::new();
+        write_csv(&mut out, &["Wire Gauge", "Voltage Drop (V)"], &rows).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Wire Gauge,Voltage Drop (V)\n12 AWG,0.30904\n\"Kitchen, north\",\n"
+        );
+    }
+
+    #[test]
+    fn writes_markdown_with_pipes_escaped() {
+        let rows = vec![vec!["A|B", "✗ Too much drop"]];
+        assert_eq!(
+            markdown_table(&["Circuit", "Status"], &rows),
+            "| Circuit | Status |\n| --- | --- |\n| A\\|B | ✗ Too much drop |\n"
+        );
+    }
+}
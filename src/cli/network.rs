@@ -0,0 +1,282 @@
+//! `network` mode: node voltages and branch sizing for a radial network
+
+use std::path::PathBuf;
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{size_network, solve_network, Circuit, Network, NetworkResult, WireGauge};
+
+use super::{
+    add_row, check_problems, check_status, exit_with_error, format_number, format_optional,
+    is_derated, print_csv, print_json, print_markdown, print_voltage, set_error_format, status,
+    Format, WireArgs, WireInputs,
+};
+
+/// Node voltages, branch currents and suggested gauges for a radial network
+#[derive(Args, Debug)]
+pub struct NetworkArgs {
+    /// Network file (TOML) describing the branches and loads
+    pub file: PathBuf,
+
+    /// Source voltage in volts (line-to-line for split-phase and three-phase
+    /// systems)
+    #[arg(short, long)]
+    pub voltage: f64,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &NetworkArgs) {
+    set_error_format(args.wire.format);
+    let contents = std::fs::read_to_string(&args.file)
+        .unwrap_or_else(|err| exit_with_error(format!("{}: {}", args.file.display(), err)));
+    let network = Network::from_toml(&contents, args.wire.standard)
+        .unwrap_or_else(|err| exit_with_error(err));
+
+    let circuit = args.wire.circuit(args.voltage, 0.0, 0.0);
+    let result = solve_network(&circuit, &network);
+    let suggested = size_network(&circuit, &network, &args.wire.gauges());
+    let derated = is_derated(&circuit);
+
+    let mut branch_header = vec![
+        "Branch",
+        "Length (ft)",
+        "Wire Gauge",
+        "Material",
+        "Current (A)",
+        "Voltage Drop (V)",
+        "Drop (%)",
+        "Ampacity (A)",
+    ];
+    if derated {
+        branch_header.push("Derated (A)");
+    }
+    branch_header.extend(["Status", "Suggested Gauge"]);
+    let branch_rows = |full_precision: bool| -> Vec<Vec<String>> {
+        result
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(index, branch_result)| {
+                let branch = &branch_result.branch;
+                let mut cells = vec![
+                    format!("{} → {}", branch.from, branch.to),
+                    branch.distance.to_string(),
+                    branch.gauge.to_string(),
+                    branch.material.unwrap_or(circuit.material).to_string(),
+                    format_number(branch_result.current, 2, full_precision),
+                    format_number(branch_result.drop.voltage_drop, 3, full_precision),
+                    format_number(branch_result.drop.drop_percentage, 2, full_precision),
+                    format_optional(branch_result.drop.ampacity, 0, full_precision),
+                ];
+                if derated {
+                    let derated_ampacity = branch_result.drop.derated_ampacity;
+                    cells.push(format_optional(derated_ampacity, 1, full_precision));
+                }
+                cells.push(check_status(
+                    true,
+                    branch_result.drop.within_ampacity,
+                    branch_result.drop.ampacity,
+                ));
+                cells.push(match suggested {
+                    Some(ref gauges) => gauges[index].to_string(),
+                    None if full_precision => String::new(),
+                    None => "-".to_string(),
+                });
+                cells
+            })
+            .collect()
+    };
+
+    let node_header = [
+        "Node",
+        "Voltage (V)",
+        "Voltage Drop (V)",
+        "Drop (%)",
+        "Status",
+    ];
+    let node_rows = |full_precision: bool| -> Vec<Vec<String>> {
+        result
+            .nodes
+            .iter()
+            .map(|node| {
+                let problems: &[&str] = if node.within_max_drop {
+                    &[]
+                } else {
+                    &["Too much drop"]
+                };
+                vec![
+                    node.node.clone(),
+                    format_number(node.voltage, 3, full_precision),
+                    format_number(node.voltage_drop, 3, full_precision),
+                    format_number(node.drop_percentage, 2, full_precision),
+                    status(problems),
+                ]
+            })
+            .collect()
+    };
+
+    // CSV and Markdown give the branch table, a blank line and the node table
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &result, suggested.as_deref()),
+        Format::Csv => {
+            print_csv(&branch_header, &branch_rows(true));
+            println!();
+            return print_csv(&node_header, &node_rows(true));
+        }
+        Format::Markdown => {
+            print_markdown(&branch_header, &branch_rows(false));
+            println!();
+            return print_markdown(&node_header, &node_rows(false));
+        }
+        Format::Table => {}
+    }
+
+    let mut branch_table = Table::new();
+    add_row(&mut branch_table, &branch_header);
+    for cells in branch_rows(false) {
+        add_row(&mut branch_table, &cells);
+    }
+    let mut node_table = Table::new();
+    add_row(&mut node_table, &node_header);
+    for cells in node_rows(false) {
+        add_row(&mut node_table, &cells);
+    }
+
+    println!("\n=== Radial Network Voltage Drop Calculator ===\n");
+    println!("Input Parameters:");
+    println!("  Network: {}", args.file.display());
+    print_voltage("Source Voltage", &circuit);
+    println!(
+        "  Branches: {}, loads: {} ({} A total)",
+        network.branches.len(),
+        network.loads.len(),
+        network.loads.iter().map(|load| load.current).sum::<f64>()
+    );
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    println!("Branches:");
+    branch_table.printstd();
+    println!();
+    println!("Nodes:");
+    node_table.printstd();
+
+    println!();
+    if result.acceptable {
+        println!(
+            "All nodes are within the {}% maximum drop.",
+            circuit.max_drop
+        );
+    } else if suggested.is_some() {
+        println!(
+            "The suggested gauges bring every node within the {}% maximum drop.",
+            circuit.max_drop
+        );
+    } else {
+        println!(
+            "WARNING: No combination of gauges meets the voltage drop and ampacity requirements!"
+        );
+    }
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    branches: Vec<JsonBranch>,
+    nodes: Vec<JsonNode>,
+    acceptable: bool,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    network: String,
+    voltage: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+/// A branch; `suggested_gauge` is `null` when no combination of gauges
+/// passes
+#[derive(Serialize)]
+struct JsonBranch {
+    from: String,
+    to: String,
+    length: f64,
+    gauge: String,
+    material: &'static str,
+    current: f64,
+    voltage_drop: f64,
+    drop_percentage: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    problems: Vec<&'static str>,
+    suggested_gauge: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonNode {
+    node: String,
+    voltage: f64,
+    voltage_drop: f64,
+    drop_percentage: f64,
+    within_max_drop: bool,
+}
+
+/// Print the inputs, every branch and node, and the suggested gauges as one
+/// JSON document
+fn print_report(
+    args: &NetworkArgs,
+    circuit: &Circuit,
+    result: &NetworkResult,
+    suggested: Option<&[WireGauge]>,
+) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            network: args.file.display().to_string(),
+            voltage: circuit.voltage,
+            wire: args.wire.inputs(circuit),
+        },
+        branches: result
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(index, branch_result)| {
+                let branch = &branch_result.branch;
+                JsonBranch {
+                    from: branch.from.clone(),
+                    to: branch.to.clone(),
+                    length: branch.distance,
+                    gauge: branch.gauge.to_string(),
+                    material: branch.material.unwrap_or(circuit.material).id(),
+                    current: branch_result.current,
+                    voltage_drop: branch_result.drop.voltage_drop,
+                    drop_percentage: branch_result.drop.drop_percentage,
+                    ampacity: branch_result.drop.ampacity,
+                    derated_ampacity: branch_result.drop.derated_ampacity,
+                    problems: check_problems(
+                        true,
+                        branch_result.drop.within_ampacity,
+                        branch_result.drop.ampacity,
+                    ),
+                    suggested_gauge: suggested.map(|gauges| gauges[index].to_string()),
+                }
+            })
+            .collect(),
+        nodes: result
+            .nodes
+            .iter()
+            .map(|node| JsonNode {
+                node: node.node.clone(),
+                voltage: node.voltage,
+                voltage_drop: node.voltage_drop,
+                drop_percentage: node.drop_percentage,
+                within_max_drop: node.within_max_drop,
+            })
+            .collect(),
+        acceptable: result.acceptable,
+    });
+}
@@ -0,0 +1,62 @@
+//! Project mode: every circuit of an installation, from a TOML or YAML file
+
+use std::path::Path;
+
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use wgrs::{ExplicitSettings, Project};
+
+use super::batch::{print_summaries, summarize};
+use super::{exit_with_error, set_error_format, WireArgs};
+
+pub fn run(path: &Path, wire: &WireArgs, matches: &ArgMatches) {
+    set_error_format(wire.format);
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| exit_with_error(format!("{}: {}", path.display(), err)));
+    let base = wire.circuit(0.0, 0.0, 0.0);
+    let explicit = explicit_settings(matches);
+    let yaml = path
+        .extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml");
+    let project = if yaml {
+        Project::from_yaml(&contents, &base, &explicit, wire.standard)
+    } else {
+        Project::from_toml(&contents, &base, &explicit, wire.standard)
+    }
+    .unwrap_or_else(|err| exit_with_error(err));
+
+    let gauges = wire.gauges();
+    let summaries: Vec<_> = project
+        .circuits
+        .into_iter()
+        .map(|circuit| summarize(circuit.name, circuit.circuit, circuit.gauge, &gauges))
+        .collect();
+
+    let mut parameters = vec![
+        format!("Project: {}", path.display()),
+        format!("Circuits: {}", summaries.len()),
+        format!("Standard: {}", wire.standard),
+    ];
+    if let Some(ref gauges) = wire.gauges {
+        parameters.push(format!("Filtered Gauges: [{}]", gauges.join(", ")));
+    }
+    print_summaries("Project Voltage Drop Summary", &parameters, &summaries, true, wire.format);
+}
+
+/// The installation options given on the command line, which override the
+/// project file's defaults
+fn explicit_settings(matches: &ArgMatches) -> ExplicitSettings {
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    ExplicitSettings {
+        max_drop: given("max_drop"),
+        material: given("material"),
+        temperature: given("temperature"),
+        insulation_rating: given("insulation_rating"),
+        ambient: given("ambient"),
+        conductors_in_raceway: given("conductors_in_raceway"),
+        system: given("system"),
+        ac: given("ac"),
+        power_factor: given("power_factor"),
+        conduit: given("conduit"),
+    }
+}
@@ -0,0 +1,344 @@
+//! `--report`: a standalone HTML calculation report
+
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use wgrs::{Circuit, Load, Standard, System, AMBIENT_BASE, BASE_TEMPERATURE, CMIL_PER_MM2};
+
+/// Everything the report shows, as already laid out for the terminal
+pub struct Report<'a> {
+    /// Calculator title
+    pub title: &'a str,
+    /// The circuit evaluated
+    pub circuit: &'a Circuit,
+    /// The load model
+    pub load: Load,
+    /// Wire sizing standard of the gauges
+    pub standard: Standard,
+    /// Input parameter lines, `Label: value`
+    pub parameters: &'a [String],
+    /// Results table header
+    pub header: &'a [&'a str],
+    /// Results table rows
+    pub rows: &'a [Vec<String>],
+    /// Whether each row's gauge passes
+    pub passed: &'a [bool],
+    /// Recommendation lines, details indented
+    pub recommendation: &'a [String],
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em auto; max-width: 60em; color: #222; }
+h1 { font-size: 1.6em; }
+h2 { font-size: 1.2em; margin-top: 1.5em; border-bottom: 1px solid #ccc; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.6em; text-align: left; }
+thead th { background: #eee; }
+tr.pass td { background: #e8f5e9; }
+tr.fail td { background: #fdecea; }
+tr.pass td:last-child { color: #1b5e20; }
+tr.fail td:last-child { color: #b71c1c; }
+.warning { color: #b71c1c; font-weight: bold; }
+@media print { body { margin: 0; max-width: none; } }
+";
+
+impl Report<'_> {
+    /// Write the report to `path`
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.html())
+    }
+
+    /// The report as a complete HTML page
+    fn html(&self) -> String {
+        let mut html = String::new();
+        let _ = writeln!(html, "<!DOCTYPE html>");
+        let _ = writeln!(html, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+        let _ = writeln!(html, "<title>{}</title>", escape(self.title));
+        let _ = writeln!(html, "<style>\n{}</style>\n</head>\n<body>", STYLE);
+        let _ = writeln!(html, "<h1>{}</h1>", escape(self.title));
+        let _ = writeln!(
+            html,
+            "<p>Generated by wgrs {}.</p>",
+            escape(env!("CARGO_PKG_VERSION"))
+        );
+
+        let _ = writeln!(html, "<h2>Input Parameters</h2>\n<table>");
+        for parameter in self.parameters {
+            let (label, value) = parameter.split_once(": ").unwrap_or((parameter, ""));
+            let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", escape(label), escape(value));
+        }
+        let _ = writeln!(html, "</table>");
+
+        let _ = writeln!(html, "<h2>Formulas</h2>\n<ul>");
+        for formula in self.formulas() {
+            let _ = writeln!(html, "<li>{}</li>", escape(&formula));
+        }
+        let _ = writeln!(html, "</ul>");
+
+        let _ = writeln!(html, "<h2>Results</h2>\n<table>\n<thead><tr>");
+        for cell in self.header {
+            let _ = writeln!(html, "<th>{}</th>", escape(cell));
+        }
+        let _ = writeln!(html, "</tr></thead>\n<tbody>");
+        for (row, &passed) in self.rows.iter().zip(self.passed) {
+            let _ = write!(html, "<tr class=\"{}\">", if passed { "pass" } else { "fail" });
+            for cell in row {
+                let _ = write!(html, "<td>{}</td>", escape(cell));
+            }
+            let _ = writeln!(html, "</tr>");
+        }
+        let _ = writeln!(html, "</tbody>\n</table>");
+
+        let _ = writeln!(html, "<h2>Recommendation</h2>");
+        if let Some((first, details)) = self.recommendation.split_first() {
+            if details.is_empty() {
+                let _ = writeln!(html, "<p class=\"warning\">{}</p>", escape(first));
+            } else {
+                let _ = writeln!(html, "<p><strong>{}</strong></p>\n<ul>", escape(first));
+                for detail in details {
+                    let _ = writeln!(html, "<li>{}</li>", escape(detail.trim()));
+                }
+                let _ = writeln!(html, "</ul>");
+            }
+        }
+
+        let _ = writeln!(html, "<h2>Resistance Data and Temperature Basis</h2>\n<ul>");
+        for note in self.basis() {
+            let _ = writeln!(html, "<li>{}</li>", escape(&note));
+        }
+        let _ = writeln!(html, "</ul>\n</body>\n</html>");
+        html
+    }
+
+    /// The formulas behind the results, for this circuit and load
+    fn formulas(&self) -> Vec<String> {
+        let circuit = self.circuit;
+        let mut formulas = vec![
+            format!(
+                "Resistivity at the conductor temperature: ρ(T) = ρ({0}°C) × (1 + α × (T − {0}))",
+                BASE_TEMPERATURE
+            ),
+            "Conductor resistance per 1000 ft: R = ρ(T) × k × 1000 / A, with A the area in \
+             circular mils and k the stranding factor"
+                .to_string(),
+            match circuit.system {
+                System::Dc | System::SinglePhase2Wire | System::SplitPhase => {
+                    "Circuit length: L = 2 × one-way distance (out and back)".to_string()
+                }
+                System::ThreePhase3Wire | System::ThreePhase4Wire => {
+                    "Circuit length: L = √3 × one-way distance (three-phase line-to-line drop)"
+                        .to_string()
+                }
+            },
+        ];
+        match circuit.ac {
+            Some(_) => {
+                formulas.push(
+                    "Effective impedance per 1000 ft: Z = R × cos θ + X × sin θ, with cos θ the \
+                     power factor"
+                        .to_string(),
+                );
+                formulas.push("Voltage drop: Vd = I × Z × L / 1000".to_string());
+            }
+            None => formulas.push("Voltage drop: Vd = I × R × L / 1000".to_string()),
+        }
+        formulas.push(if circuit.voltage_min.is_some() {
+            "Drop percentage: Vd / Vmin × 100, at the minimum source voltage".to_string()
+        } else {
+            "Drop percentage: Vd / V × 100".to_string()
+        });
+        // The load formulas as `Load::operating_current` applies them: power
+        // per line over √3 on three-phase and the power factor on AC, and the
+        // per-phase load resistance times √3 on three-phase
+        let three_phase = circuit.system.phase_factor() != 1.0;
+        let run = format!("Zrun = {} × L / 1000", if circuit.ac.is_some() { "Z" } else { "R" });
+        match self.load {
+            Load::Current(_) => {}
+            Load::Power(_) => {
+                let line_power = match (three_phase, circuit.ac.is_some()) {
+                    (false, false) => None,
+                    (true, false) => Some("P / √3"),
+                    (false, true) => Some("P / cos θ"),
+                    (true, true) => Some("P / (√3 × cos θ)"),
+                };
+                let p = if line_power.is_some() { "P′" } else { "P" };
+                let mut formula = format!(
+                    "Constant-power load current: I = (V − √(V² − 4 × Zrun × {})) / (2 × Zrun), \
+                     with {}",
+                    p, run
+                );
+                if let Some(line_power) = line_power {
+                    let _ = write!(formula, ", P′ = {}", line_power);
+                }
+                let _ = write!(formula, " and voltage collapse when V² < 4 × Zrun × {}", p);
+                formulas.push(formula);
+            }
+            Load::Resistance(_) => formulas.push(format!(
+                "Resistive load current: I = V / ({} + Zrun), with {}{}",
+                if three_phase { "Rload × √3" } else { "Rload" },
+                run,
+                if three_phase { " and Rload per phase" } else { "" }
+            )),
+        }
+        formulas.push(format!(
+            "Power loss: P = I² × R × {} conductors × one-way distance / 1000",
+            circuit.system.loaded_conductors()
+        ));
+        formulas.push(
+            "Ampacity: NEC 310.16 × ambient correction (NEC 310.15(B)) × adjustment for more \
+             than three current-carrying conductors (NEC 310.15(C)(1))"
+                .to_string(),
+        );
+        formulas
+    }
+
+    /// Where the resistances come from and the temperatures they are for
+    fn basis(&self) -> Vec<String> {
+        let circuit = self.circuit;
+        let material = circuit.material;
+        let mut notes = vec![
+            format!(
+                "{} resistivity: {} Ω·cmil/ft at {}°C, temperature coefficient α = {} per °C",
+                material,
+                material.resistivity(),
+                BASE_TEMPERATURE,
+                material.temperature_coefficient()
+            ),
+            format!(
+                "Conductor temperature: {}°C, giving ρ = {:.3} Ω·cmil/ft",
+                circuit.temperature,
+                material.resistivity_at(circuit.temperature)
+            ),
+        ];
+        notes.push(match self.standard {
+            Standard::Awg => "Areas: AWG sizes from the AWG diameter formula \
+                              d = 0.005 in × 92^((36 − n) / 39); kcmil sizes from their nominal \
+                              area; 8 AWG and larger stranded with a 2% lay allowance \
+                              (k = 1.02) to match NEC Chapter 9 Table 8, smaller sizes solid"
+                .to_string(),
+            Standard::Metric => format!(
+                "Areas: IEC 60228 nominal cross-sections, at {} circular mils per mm²",
+                CMIL_PER_MM2
+            ),
+        });
+        if let Some(ac) = circuit.ac {
+            notes.push(format!(
+                "AC resistance ratios and reactances: NEC Chapter 9 Table 9 (600 V cables, \
+                 60 Hz, 75°C) for {} conduit",
+                ac.conduit
+            ));
+        }
+        notes.push(format!(
+            "Ampacity: NEC 310.16 {} column, based on {}°C ambient",
+            circuit.insulation_rating,
+            AMBIENT_BASE
+        ));
+        notes
+    }
+}
+
+/// Escape text for HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgrs::AcParameters;
+
+    fn report<'a>(
+        circuit: &'a Circuit,
+        load: Load,
+        rows: &'a [Vec<String>],
+        passed: &'a [bool],
+    ) -> Report<'a> {
+        Report {
+            title: "Test <report>",
+            circuit,
+            load,
+            standard: Standard::Awg,
+            parameters: &[],
+            header: &["Wire Gauge", "Status"],
+            rows,
+            passed,
+            recommendation: &[],
+        }
+    }
+
+    fn formulas(circuit: &Circuit, load: Load) -> String {
+        report(circuit, load, &[], &[]).formulas().join("\n")
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape("<a href=\"x\">R & X</a>"),
+            "&lt;a href=&quot;x&quot;&gt;R &amp; X&lt;/a&gt;"
+        );
+        assert_eq!(escape("Vd = I × R"), "Vd = I × R");
+    }
+
+    #[test]
+    fn styles_passing_and_failing_rows() {
+        let circuit = Circuit::new(12.0, 10.0, 15.0);
+        let rows = [
+            vec!["12 AWG".to_string(), "✗ Too much drop".to_string()],
+            vec!["10 AWG".to_string(), "✓ OK".to_string()],
+        ];
+        let html = report(&circuit, Load::Current(10.0), &rows, &[false, true]).html();
+        assert!(html.contains("<tr class=\"fail\"><td>12 AWG</td><td>✗ Too much drop</td></tr>"));
+        assert!(html.contains("<tr class=\"pass\"><td>10 AWG</td><td>✓ OK</td></tr>"));
+        assert!(html.contains("<title>Test &lt;report&gt;</title>"));
+    }
+
+    #[test]
+    fn shows_the_formulas_for_the_system() {
+        let dc = Circuit::new(12.0, 10.0, 15.0);
+        let dc = formulas(&dc, Load::Current(10.0));
+        assert!(dc.contains("L = 2 × one-way distance"));
+        assert!(dc.contains("Vd = I × R × L / 1000"));
+        assert!(dc.contains("× 2 conductors"));
+        assert!(!dc.contains("load current"));
+
+        let three_phase = Circuit {
+            system: System::ThreePhase4Wire,
+            ac: Some(AcParameters::default()),
+            voltage_min: Some(200.0),
+            ..Circuit::new(208.0, 10.0, 100.0)
+        };
+        let three_phase = formulas(&three_phase, Load::Current(10.0));
+        assert!(three_phase.contains("L = √3 × one-way distance"));
+        assert!(three_phase.contains("Z = R × cos θ + X × sin θ"));
+        assert!(three_phase.contains("Vd = I × Z × L / 1000"));
+        assert!(three_phase.contains("Vd / Vmin × 100"));
+        assert!(three_phase.contains("× 3 conductors"));
+    }
+
+    #[test]
+    fn shows_the_formulas_for_the_load() {
+        let dc = Circuit::new(12.0, 0.0, 15.0);
+        let power = formulas(&dc, Load::Power(100.0));
+        assert!(power.contains("Constant-power load current"));
+        assert!(power.contains("collapse when V² < 4 × Zrun × P"));
+        assert!(!power.contains("P′"));
+        let resistance = formulas(&dc, Load::Resistance(2.0));
+        assert!(resistance.contains("I = V / (Rload + Zrun), with Zrun = R × L / 1000"));
+
+        let three_phase = Circuit {
+            system: System::ThreePhase3Wire,
+            ac: Some(AcParameters::default()),
+            ..Circuit::new(480.0, 0.0, 100.0)
+        };
+        let power = formulas(&three_phase, Load::Power(10_000.0));
+        assert!(power.contains("P′ = P / (√3 × cos θ)"));
+        assert!(power.contains("Zrun = Z × L / 1000"));
+        let resistance = formulas(&three_phase, Load::Resistance(20.0));
+        assert!(resistance.contains("I = V / (Rload × √3 + Zrun)"));
+        assert!(resistance.contains("Rload per phase"));
+    }
+}
@@ -0,0 +1,215 @@
+//! `segments` mode: cumulative drop along a run of several segments
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{calculate_run, Circuit, RunResult, Segment, COMBINED_MAX_DROP};
+
+use super::{
+    add_row, check_problems, check_status, exit_with_error, format_number, format_optional,
+    is_derated, print_csv, print_json, print_markdown, print_voltage, set_error_format, Format,
+    WireArgs, WireInputs,
+};
+
+/// Drop along a run of segments in series, each with its own gauge
+#[derive(Args, Debug)]
+pub struct SegmentsArgs {
+    /// Voltage in volts (line-to-line for split-phase and three-phase systems)
+    #[arg(short, long)]
+    pub voltage: f64,
+
+    /// A segment as length:gauge:current[:material], from the source
+    /// outwards (repeat for each segment, e.g. -s 100:2/0:100 -s 40:10:30)
+    #[arg(short, long = "segment", required = true)]
+    pub segments: Vec<String>,
+
+    /// Maximum combined voltage drop percentage for the whole run
+    #[arg(long, default_value_t = COMBINED_MAX_DROP)]
+    pub combined_max_drop: f64,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &SegmentsArgs) {
+    set_error_format(args.wire.format);
+    if args.wire.gauges.is_some() {
+        exit_with_error("--gauges does not apply to segments; give each segment's gauge instead");
+    }
+    let circuit = args.wire.circuit(args.voltage, 0.0, 0.0);
+    let segments: Vec<Segment> = args
+        .segments
+        .iter()
+        .map(|segment| Segment::parse(segment, args.wire.standard))
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|err| exit_with_error(err));
+    let run = calculate_run(&circuit, &segments, args.combined_max_drop);
+    let derated = is_derated(&circuit);
+
+    let mut header = vec![
+        "Segment",
+        "Length (ft)",
+        "Wire Gauge",
+        "Material",
+        "Current (A)",
+        "Voltage Drop (V)",
+        "Drop (%)",
+        "Cumulative (V)",
+        "Cumulative (%)",
+        "Ampacity (A)",
+    ];
+    if derated {
+        header.push("Derated (A)");
+    }
+    header.push("Status");
+
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        run.segments
+            .iter()
+            .enumerate()
+            .map(|(number, result)| {
+                let segment = &result.segment;
+                let mut cells = vec![
+                    (number + 1).to_string(),
+                    segment.distance.to_string(),
+                    segment.gauge.to_string(),
+                    segment.material.unwrap_or(circuit.material).to_string(),
+                    segment.current.to_string(),
+                    format_number(result.drop.voltage_drop, 3, full_precision),
+                    format_number(result.drop.drop_percentage, 2, full_precision),
+                    format_number(result.cumulative_drop, 3, full_precision),
+                    format_number(result.cumulative_percentage, 2, full_precision),
+                    format_optional(result.drop.ampacity, 0, full_precision),
+                ];
+                if derated {
+                    cells.push(format_optional(
+                        result.drop.derated_ampacity,
+                        1,
+                        full_precision,
+                    ));
+                }
+                cells.push(check_status(
+                    result.drop.within_max_drop,
+                    result.drop.within_ampacity,
+                    result.drop.ampacity,
+                ));
+                cells
+            })
+            .collect()
+    };
+
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &run),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== Multi-Segment Voltage Drop Calculator ===\n");
+    println!("Input Parameters:");
+    print_voltage("Voltage", &circuit);
+    println!("  Segments: {}", run.segments.len());
+    println!("  Max Combined Drop: {}%", args.combined_max_drop);
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    table.printstd();
+
+    println!();
+    println!(
+        "Total voltage drop: {:.3} V ({:.2}%), {:.3} V at the end of the run",
+        run.voltage_drop,
+        run.drop_percentage,
+        args.voltage - run.voltage_drop
+    );
+    if run.within_combined_max {
+        println!("  ✓ Within the {}% combined limit", args.combined_max_drop);
+    } else {
+        println!("  ✗ Exceeds the {}% combined limit", args.combined_max_drop);
+    }
+    if !run.acceptable {
+        println!("WARNING: The run does not meet the voltage drop and ampacity requirements!");
+    }
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    segments: Vec<JsonSegment>,
+    voltage_drop: f64,
+    drop_percentage: f64,
+    end_voltage: f64,
+    within_combined_max: bool,
+    acceptable: bool,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    voltage: f64,
+    combined_max_drop: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+struct JsonSegment {
+    length: f64,
+    gauge: String,
+    material: &'static str,
+    current: f64,
+    voltage_drop: f64,
+    drop_percentage: f64,
+    cumulative_drop: f64,
+    cumulative_percentage: f64,
+    end_voltage: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    acceptable: bool,
+    problems: Vec<&'static str>,
+}
+
+/// Print the inputs, every segment and the whole run as one JSON document
+fn print_report(args: &SegmentsArgs, circuit: &Circuit, run: &RunResult) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            voltage: circuit.voltage,
+            combined_max_drop: args.combined_max_drop,
+            wire: args.wire.inputs(circuit),
+        },
+        segments: run
+            .segments
+            .iter()
+            .map(|result| JsonSegment {
+                length: result.segment.distance,
+                gauge: result.segment.gauge.to_string(),
+                material: result.segment.material.unwrap_or(circuit.material).id(),
+                current: result.segment.current,
+                voltage_drop: result.drop.voltage_drop,
+                drop_percentage: result.drop.drop_percentage,
+                cumulative_drop: result.cumulative_drop,
+                cumulative_percentage: result.cumulative_percentage,
+                end_voltage: result.end_voltage,
+                ampacity: result.drop.ampacity,
+                derated_ampacity: result.drop.derated_ampacity,
+                acceptable: result.drop.acceptable,
+                problems: check_problems(
+                    result.drop.within_max_drop,
+                    result.drop.within_ampacity,
+                    result.drop.ampacity,
+                ),
+            })
+            .collect(),
+        voltage_drop: run.voltage_drop,
+        drop_percentage: run.drop_percentage,
+        end_voltage: circuit.voltage - run.voltage_drop,
+        within_combined_max: run.within_combined_max,
+        acceptable: run.acceptable,
+    });
+}
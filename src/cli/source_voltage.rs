@@ -0,0 +1,179 @@
+//! `source-voltage` mode: supply voltage needed for a target load voltage
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{source_voltage, Circuit, SourceVoltageResult};
+
+use super::{
+    add_row, check_problems, check_status, format_number, format_optional, is_derated,
+    parse_positive, print_csv, print_json, print_markdown, print_voltage, set_error_format, Format,
+    WireArgs, WireInputs,
+};
+
+/// Source voltage per gauge for a known load voltage, current and distance
+#[derive(Args, Debug)]
+pub struct SourceVoltageArgs {
+    /// Voltage required at the load in volts (line-to-line for split-phase
+    /// and three-phase systems)
+    #[arg(short = 'l', long, value_parser = parse_positive)]
+    pub load_voltage: f64,
+
+    /// Current in amps
+    #[arg(short, long, value_parser = parse_positive)]
+    pub current: f64,
+
+    /// One-way distance in feet
+    #[arg(short, long, value_parser = parse_positive)]
+    pub distance: f64,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &SourceVoltageArgs) {
+    set_error_format(args.wire.format);
+    let circuit = args
+        .wire
+        .circuit(args.load_voltage, args.current, args.distance);
+    let gauges = args.wire.gauges();
+    let results = source_voltage(&circuit, &gauges);
+    let derated = is_derated(&circuit);
+
+    let mut header = vec![
+        "Wire Gauge",
+        "Resistance (Ω)",
+        "Voltage Drop (V)",
+        "Source Voltage (V)",
+        "Drop (%)",
+        "Ampacity (A)",
+    ];
+    if derated {
+        header.push("Derated (A)");
+    }
+    header.push("Status");
+
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        results
+            .iter()
+            .map(|result| {
+                let mut cells = vec![
+                    result.gauge.to_string(),
+                    format_number(result.total_resistance, 4, full_precision),
+                    format_number(result.voltage_drop, 3, full_precision),
+                    format_number(result.source_voltage, 3, full_precision),
+                    format_number(result.drop_percentage, 2, full_precision),
+                    format_optional(result.ampacity, 0, full_precision),
+                ];
+                if derated {
+                    cells.push(format_optional(result.derated_ampacity, 1, full_precision));
+                }
+                cells.push(check_status(
+                    result.within_max_drop,
+                    result.within_ampacity,
+                    result.ampacity,
+                ));
+                cells
+            })
+            .collect()
+    };
+
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &results),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== Wire Gauge Source Voltage Calculator ===\n");
+    println!("Input Parameters:");
+    print_voltage("Load Voltage", &circuit);
+    println!("  Current: {} A", circuit.current);
+    println!("  Distance: {} ft (one way)", circuit.distance);
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    table.printstd();
+
+    println!();
+    if let Some(best) = results.iter().find(|result| result.acceptable) {
+        println!("Recommended gauge: {}", best.gauge);
+        println!("  Source voltage: {:.3} V", best.source_voltage);
+        println!(
+            "  Voltage drop: {:.3} V ({:.2}%)",
+            best.voltage_drop, best.drop_percentage
+        );
+    } else {
+        println!("WARNING: No gauge meets both the voltage drop and ampacity requirements!");
+    }
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    rows: Vec<JsonRow>,
+    recommended_gauge: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    load_voltage: f64,
+    current: f64,
+    distance: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+struct JsonRow {
+    gauge: String,
+    resistance: f64,
+    voltage_drop: f64,
+    source_voltage: f64,
+    drop_percentage: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    acceptable: bool,
+    problems: Vec<&'static str>,
+}
+
+/// Print the inputs, every gauge and the recommendation as one JSON document
+fn print_report(args: &SourceVoltageArgs, circuit: &Circuit, results: &[SourceVoltageResult]) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            load_voltage: circuit.voltage,
+            current: circuit.current,
+            distance: circuit.distance,
+            wire: args.wire.inputs(circuit),
+        },
+        rows: results
+            .iter()
+            .map(|result| JsonRow {
+                gauge: result.gauge.to_string(),
+                resistance: result.total_resistance,
+                voltage_drop: result.voltage_drop,
+                source_voltage: result.source_voltage,
+                drop_percentage: result.drop_percentage,
+                ampacity: result.ampacity,
+                derated_ampacity: result.derated_ampacity,
+                acceptable: result.acceptable,
+                problems: check_problems(
+                    result.within_max_drop,
+                    result.within_ampacity,
+                    result.ampacity,
+                ),
+            })
+            .collect(),
+        recommended_gauge: results
+            .iter()
+            .find(|result| result.acceptable)
+            .map(|result| result.gauge.to_string()),
+    });
+}
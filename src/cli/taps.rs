@@ -0,0 +1,208 @@
+//! `taps` mode: voltage at loads tapped along a single run
+
+use clap::Args;
+use prettytable::Table;
+use serde::Serialize;
+use wgrs::{calculate_taps, Circuit, Tap, TapsResult};
+
+use super::{
+    add_row, check_problems, check_status, format_number, format_optional, is_derated, print_csv,
+    print_json, print_markdown, print_voltage, set_error_format, Format, WireArgs, WireInputs,
+};
+
+/// Voltage at each of several loads tapped along one run
+#[derive(Args, Debug)]
+pub struct TapsArgs {
+    /// Voltage in volts (line-to-line for split-phase and three-phase systems)
+    #[arg(short, long)]
+    pub voltage: f64,
+
+    /// A load as position:current, with the position in feet from the
+    /// source (repeat for each tap, e.g. --tap 10:0.5 --tap 20:0.5)
+    #[arg(long = "tap", required = true)]
+    pub taps: Vec<Tap>,
+
+    #[command(flatten)]
+    pub wire: WireArgs,
+}
+
+pub fn run(args: &TapsArgs) {
+    set_error_format(args.wire.format);
+    let total_current: f64 = args.taps.iter().map(|tap| tap.current).sum();
+    let circuit = args.wire.circuit(args.voltage, total_current, 0.0);
+    let gauges = args.wire.gauges();
+    let results = calculate_taps(&circuit, &args.taps, &gauges);
+    let derated = is_derated(&circuit);
+
+    let tap_headers: Vec<String> = args
+        .taps
+        .iter()
+        .enumerate()
+        .map(|(number, tap)| format!("Tap {} @ {} ft (V)", number + 1, tap.position))
+        .collect();
+
+    let mut header = vec!["Wire Gauge"];
+    header.extend(tap_headers.iter().map(String::as_str));
+    header.extend(["Worst Drop (V)", "Worst Drop (%)", "Ampacity (A)"]);
+    if derated {
+        header.push("Derated (A)");
+    }
+    header.push("Status");
+
+    let rows = |full_precision: bool| -> Vec<Vec<String>> {
+        results
+            .iter()
+            .map(|result| {
+                let mut cells = vec![result.gauge.to_string()];
+                cells.extend(
+                    result
+                        .tap_voltages
+                        .iter()
+                        .map(|&voltage| format_number(voltage, 3, full_precision)),
+                );
+                cells.extend([
+                    format_number(result.voltage_drop, 3, full_precision),
+                    format_number(result.drop_percentage, 2, full_precision),
+                    format_optional(result.ampacity, 0, full_precision),
+                ]);
+                if derated {
+                    cells.push(format_optional(result.derated_ampacity, 1, full_precision));
+                }
+                cells.push(check_status(
+                    result.within_max_drop,
+                    result.within_ampacity,
+                    result.ampacity,
+                ));
+                cells
+            })
+            .collect()
+    };
+
+    match args.wire.format {
+        Format::Json => return print_report(args, &circuit, &results),
+        Format::Csv => return print_csv(&header, &rows(true)),
+        Format::Markdown => return print_markdown(&header, &rows(false)),
+        Format::Table => {}
+    }
+
+    let mut table = Table::new();
+    add_row(&mut table, &header);
+    for cells in rows(false) {
+        add_row(&mut table, &cells);
+    }
+
+    println!("\n=== Tapped Run Voltage Drop Calculator ===\n");
+    println!("Input Parameters:");
+    print_voltage("Voltage", &circuit);
+    println!(
+        "  Taps: {} ({} A total)",
+        args.taps
+            .iter()
+            .map(|tap| format!("{} A @ {} ft", tap.current, tap.position))
+            .collect::<Vec<_>>()
+            .join(", "),
+        total_current
+    );
+    args.wire.print_parameters(&circuit);
+    println!();
+
+    table.printstd();
+
+    println!();
+    if let Some(best) = results.iter().find(|result| result.acceptable) {
+        println!("Recommended gauge: {}", best.gauge);
+        println!(
+            "  Worst tap: {} at {} ft, {:.3} V",
+            best.worst_tap + 1,
+            args.taps[best.worst_tap].position,
+            best.tap_voltages[best.worst_tap]
+        );
+        println!(
+            "  Voltage drop: {:.3} V ({:.2}%)",
+            best.voltage_drop, best.drop_percentage
+        );
+        if let Some(amps) = best.derated_ampacity {
+            println!("  Ampacity: {:.1} A at {}", amps, circuit.insulation_rating);
+        }
+    } else {
+        println!("WARNING: No gauge meets both the voltage drop and ampacity requirements!");
+    }
+}
+
+/// `--format json` document
+#[derive(Serialize)]
+struct JsonReport {
+    inputs: Inputs,
+    rows: Vec<JsonRow>,
+    recommended_gauge: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Inputs {
+    voltage: f64,
+    taps: Vec<TapInput>,
+    total_current: f64,
+    #[serde(flatten)]
+    wire: WireInputs,
+}
+
+#[derive(Serialize)]
+struct TapInput {
+    position: f64,
+    current: f64,
+}
+
+/// A gauge's row; `worst_tap` counts from 1, as in the table
+#[derive(Serialize)]
+struct JsonRow {
+    gauge: String,
+    tap_voltages: Vec<f64>,
+    worst_tap: usize,
+    voltage_drop: f64,
+    drop_percentage: f64,
+    ampacity: Option<f64>,
+    derated_ampacity: Option<f64>,
+    acceptable: bool,
+    problems: Vec<&'static str>,
+}
+
+/// Print the inputs, every gauge and the recommendation as one JSON document
+fn print_report(args: &TapsArgs, circuit: &Circuit, results: &[TapsResult]) {
+    print_json(&JsonReport {
+        inputs: Inputs {
+            voltage: circuit.voltage,
+            taps: args
+                .taps
+                .iter()
+                .map(|tap| TapInput {
+                    position: tap.position,
+                    current: tap.current,
+                })
+                .collect(),
+            total_current: circuit.current,
+            wire: args.wire.inputs(circuit),
+        },
+        rows: results
+            .iter()
+            .map(|result| JsonRow {
+                gauge: result.gauge.to_string(),
+                tap_voltages: result.tap_voltages.clone(),
+                worst_tap: result.worst_tap + 1,
+                voltage_drop: result.voltage_drop,
+                drop_percentage: result.drop_percentage,
+                ampacity: result.ampacity,
+                derated_ampacity: result.derated_ampacity,
+                acceptable: result.acceptable,
+                problems: check_problems(
+                    result.within_max_drop,
+                    result.within_ampacity,
+                    result.ampacity,
+                ),
+            })
+            .collect(),
+        recommended_gauge: results
+            .iter()
+            .find(|result| result.acceptable)
+            .map(|result| result.gauge.to_string()),
+    });
+}
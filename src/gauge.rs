@@ -0,0 +1,448 @@
+//! Wire sizes and their conductor cross-sections
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::{Error, Material};
+
+/// Circular mils per square millimetre
+pub const CMIL_PER_MM2: f64 = 1973.525;
+
+/// Smallest AWG gauge in the table
+const SMALLEST_AWG: i32 = 28;
+
+/// kcmil sizes continuing the AWG table above 0000 AWG
+pub const KCMIL_SIZES: &[u32] = &[
+    250, 300, 350, 400, 500, 600, 700, 750, 800, 900, 1000, 1250, 1500, 1750, 2000,
+];
+
+/// Wire sizing standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Standard {
+    /// American Wire Gauge, continuing in kcmil above 0000 AWG
+    #[default]
+    Awg,
+    /// IEC 60228 metric sizes in mm²
+    Metric,
+}
+
+impl Standard {
+    /// Name accepted on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Standard::Awg => "awg",
+            Standard::Metric => "metric",
+        }
+    }
+
+    /// Every size in this standard, from smallest to largest
+    pub fn gauges(&self) -> &'static [WireGauge] {
+        match self {
+            Standard::Awg => wire_gauges(),
+            Standard::Metric => METRIC_SIZES,
+        }
+    }
+
+    /// The sizes evaluated when none are requested, from smallest to
+    /// largest: every size except the odd AWG sizes from 27 to 5 AWG,
+    /// which are not sold as building wire
+    pub fn default_gauges(&self) -> &'static [WireGauge] {
+        static AWG_GAUGES: OnceLock<Vec<WireGauge>> = OnceLock::new();
+
+        match self {
+            Standard::Awg => AWG_GAUGES.get_or_init(|| {
+                wire_gauges()
+                    .iter()
+                    .filter(|gauge| match gauge.size {
+                        WireSize::Awg(awg) => awg.is_common(),
+                        WireSize::Kcmil(_) | WireSize::Metric(_) => true,
+                    })
+                    .copied()
+                    .collect()
+            }),
+            Standard::Metric => METRIC_SIZES,
+        }
+    }
+
+    /// Parse a size written the way users type it for this standard,
+    /// e.g. `12`, `4/0` or `500kcmil` for AWG, or `2.5` for metric
+    pub fn parse_size(&self, s: &str) -> Result<WireSize, Error> {
+        let invalid = || Error::InvalidGauge {
+            size: s.to_string(),
+            standard: *self,
+        };
+        let s = s.trim();
+
+        match self {
+            Standard::Awg => {
+                let lower = s.to_ascii_lowercase();
+                match lower
+                    .strip_suffix("kcmil")
+                    .or_else(|| lower.strip_suffix("mcm"))
+                {
+                    Some(number) => number.trim().parse().map(WireSize::Kcmil).map_err(|_| invalid()),
+                    None => s.parse().map(WireSize::Awg),
+                }
+            }
+            Standard::Metric => {
+                let number = s
+                    .strip_suffix("mm²")
+                    .or_else(|| s.strip_suffix("mm2"))
+                    .unwrap_or(s)
+                    .trim();
+                number.parse().map(WireSize::Metric).map_err(|_| invalid())
+            }
+        }
+    }
+
+    /// Look up sizes of this standard, keeping the table order
+    ///
+    /// Returns an error for the first size that is not in the table.
+    pub fn select_gauges<S: AsRef<str>>(&self, sizes: &[S]) -> Result<Vec<WireGauge>, Error> {
+        let table = self.gauges();
+        let mut requested = Vec::with_capacity(sizes.len());
+
+        for size in sizes {
+            let size = size.as_ref();
+            let parsed = self.parse_size(size)?;
+            if !table.iter().any(|gauge| gauge.size == parsed) {
+                return Err(Error::InvalidGauge {
+                    size: size.to_string(),
+                    standard: *self,
+                });
+            }
+            requested.push(parsed);
+        }
+
+        Ok(table
+            .iter()
+            .filter(|gauge| requested.contains(&gauge.size))
+            .copied()
+            .collect())
+    }
+}
+
+impl fmt::Display for Standard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Standard::Awg => f.write_str("AWG/kcmil"),
+            Standard::Metric => f.write_str("Metric (mm²)"),
+        }
+    }
+}
+
+impl FromStr for Standard {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "awg" => Ok(Standard::Awg),
+            "metric" | "mm2" | "iec" => Ok(Standard::Metric),
+            _ => Err(Error::InvalidStandard(s.to_string())),
+        }
+    }
+}
+
+/// An AWG gauge
+///
+/// Gauges larger than 0 AWG are numbered below zero, one step per extra
+/// zero, so the number plugs straight into the AWG diameter formula:
+/// 00 (2/0) is -1, 000 (3/0) is -2 and 0000 (4/0) is -3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Awg(i32);
+
+impl Awg {
+    /// 0000 (4/0) AWG, the largest AWG gauge
+    pub const LARGEST: Awg = Awg(-3);
+
+    /// Create a gauge from its number, where 00 is -1, 000 is -2 and 0000 is -3
+    pub const fn new(number: i32) -> Self {
+        Awg(number)
+    }
+
+    /// Gauge number, where 00 is -1, 000 is -2 and 0000 is -3
+    pub fn number(&self) -> i32 {
+        self.0
+    }
+
+    /// Whether the gauge is commonly sold: the even sizes, and every size
+    /// from 3 AWG up
+    pub fn is_common(&self) -> bool {
+        self.0 % 2 == 0 || self.0 <= 3
+    }
+
+    /// Conductor diameter in inches: d = 0.005 × 92^((36 - n) / 39)
+    pub fn diameter(&self) -> f64 {
+        0.005 * 92f64.powf((36 - self.0) as f64 / 39.0)
+    }
+
+    /// Cross-sectional area in circular mils, the diameter in mils squared
+    pub fn area(&self) -> f64 {
+        (self.diameter() * 1000.0).powi(2)
+    }
+}
+
+impl fmt::Display for Awg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            f.write_str(&"0".repeat((1 - self.0) as usize))
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl FromStr for Awg {
+    type Err = Error;
+
+    /// Parse `12`, `12awg`, `0`, `00`, `000`, `0000` or `1/0` to `4/0`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidGauge {
+            size: s.to_string(),
+            standard: Standard::Awg,
+        };
+        let lower = s.trim().to_ascii_lowercase();
+        let number = lower.strip_suffix("awg").unwrap_or(&lower).trim();
+
+        if let Some(zeros) = number.strip_suffix("/0") {
+            return match zeros.parse::<i32>() {
+                Ok(zeros @ 1..=4) => Ok(Awg(1 - zeros)),
+                _ => Err(invalid()),
+            };
+        }
+        if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if number.len() > 1 && number.chars().all(|c| c == '0') {
+            return Ok(Awg(1 - number.len() as i32));
+        }
+        number.parse().map(Awg).map_err(|_| invalid())
+    }
+}
+
+/// A wire size in one of the supported standards
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WireSize {
+    /// AWG gauge
+    Awg(Awg),
+    /// Thousands of circular mils, for conductors larger than 0000 AWG
+    Kcmil(u32),
+    /// Metric cross-section in mm²
+    Metric(f64),
+}
+
+impl WireSize {
+    /// Cross-sectional area in circular mils
+    pub fn area(&self) -> f64 {
+        match self {
+            WireSize::Awg(awg) => awg.area(),
+            WireSize::Kcmil(kcmil) => *kcmil as f64 * 1000.0,
+            WireSize::Metric(mm2) => mm2 * CMIL_PER_MM2,
+        }
+    }
+
+    /// Ratio of the conductor's resistance to that of a solid conductor of
+    /// the same area
+    ///
+    /// Building wire of 8 AWG and larger, including every kcmil size, is
+    /// concentric stranded; the 2% lay allowance matches the stranded
+    /// resistances of NEC Chapter 9 Table 8. Smaller AWG sizes and metric
+    /// sizes are taken as solid.
+    pub fn stranding_factor(&self) -> f64 {
+        match self {
+            WireSize::Awg(awg) if awg.number() <= 8 => 1.02,
+            WireSize::Kcmil(_) => 1.02,
+            WireSize::Awg(_) | WireSize::Metric(_) => 1.0,
+        }
+    }
+
+    /// Size as accepted on the command line, e.g. `00`, `250kcmil` or `1.5`
+    pub fn id(&self) -> String {
+        match self {
+            WireSize::Awg(awg) => awg.to_string(),
+            WireSize::Kcmil(kcmil) => format!("{}kcmil", kcmil),
+            WireSize::Metric(mm2) => mm2.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for WireSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireSize::Awg(awg) => write!(f, "{} AWG", awg),
+            WireSize::Kcmil(kcmil) => write!(f, "{} kcmil", kcmil),
+            WireSize::Metric(mm2) => write!(f, "{} mm²", mm2),
+        }
+    }
+}
+
+/// A wire gauge and its conductor cross-section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WireGauge {
+    /// Size designation
+    pub size: WireSize,
+    /// Cross-sectional area in circular mils
+    pub area: f64,
+}
+
+impl WireGauge {
+    /// Create a gauge with the area of `size`
+    pub fn new(size: WireSize) -> Self {
+        WireGauge {
+            size,
+            area: size.area(),
+        }
+    }
+
+    /// Resistance in ohms per 1000 feet for a conductor of `material` at `temperature` °C
+    pub fn resistance(&self, material: Material, temperature: f64) -> f64 {
+        material.resistivity_at(temperature) * self.size.stranding_factor() * 1000.0 / self.area
+    }
+}
+
+impl fmt::Display for WireGauge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.size.fmt(f)
+    }
+}
+
+/// Every AWG gauge from 28 AWG to 0000 AWG, followed by [`KCMIL_SIZES`]
+///
+/// AWG areas come from the AWG diameter formula, see [`Awg::diameter`].
+/// Each material's resistance table is derived from these areas and the
+/// material's resistivity at 20°C, corrected to the conductor temperature,
+/// see [`Material::resistivity_at`].
+pub fn wire_gauges() -> &'static [WireGauge] {
+    static GAUGES: OnceLock<Vec<WireGauge>> = OnceLock::new();
+
+    GAUGES.get_or_init(|| {
+        (Awg::LARGEST.number()..=SMALLEST_AWG)
+            .rev()
+            .map(|number| WireSize::Awg(Awg(number)))
+            .chain(KCMIL_SIZES.iter().map(|&kcmil| WireSize::Kcmil(kcmil)))
+            .map(WireGauge::new)
+            .collect()
+    })
+}
+
+/// IEC 60228 metric sizes from 0.5 mm² to 630 mm², areas converted to
+/// circular mils
+pub const METRIC_SIZES: &[WireGauge] = &[
+    WireGauge { size: WireSize::Metric(0.5), area: 0.5 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(0.75), area: 0.75 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(1.0), area: 1.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(1.5), area: 1.5 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(2.5), area: 2.5 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(4.0), area: 4.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(6.0), area: 6.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(10.0), area: 10.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(16.0), area: 16.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(25.0), area: 25.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(35.0), area: 35.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(50.0), area: 50.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(70.0), area: 70.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(95.0), area: 95.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(120.0), area: 120.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(150.0), area: 150.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(185.0), area: 185.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(240.0), area: 240.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(300.0), area: 300.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(400.0), area: 400.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(500.0), area: 500.0 * CMIL_PER_MM2 },
+    WireGauge { size: WireSize::Metric(630.0), area: 630.0 * CMIL_PER_MM2 },
+];
+
+/// The AWG or kcmil gauge named `size`, for tests
+#[cfg(test)]
+pub(crate) fn gauge(size: &str) -> WireGauge {
+    Standard::Awg.select_gauges(&[size]).unwrap().remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn awg(s: &str) -> Result<Awg, Error> {
+        s.parse()
+    }
+
+    #[test]
+    fn parses_multi_zero_gauges() {
+        assert_eq!(awg("0").unwrap(), Awg(0));
+        assert_eq!(awg("00").unwrap(), Awg(-1));
+        assert_eq!(awg("000").unwrap(), Awg(-2));
+        assert_eq!(awg("0000").unwrap(), Awg::LARGEST);
+        assert_eq!(awg("1/0").unwrap(), awg("0").unwrap());
+        assert_eq!(awg("2/0").unwrap(), awg("00").unwrap());
+        assert_eq!(awg("4/0").unwrap(), Awg::LARGEST);
+    }
+
+    #[test]
+    fn parses_numbered_gauges() {
+        assert_eq!(awg("12").unwrap(), Awg(12));
+        assert_eq!(awg(" 10AWG ").unwrap(), Awg(10));
+    }
+
+    #[test]
+    fn rejects_invalid_gauges() {
+        for s in ["5/0", "0/0", "-1", "", "1.5", "twelve"] {
+            assert!(matches!(awg(s), Err(Error::InvalidGauge { .. })), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn multi_zero_gauges_round_trip() {
+        for s in ["0", "00", "000", "0000"] {
+            assert_eq!(awg(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parses_kcmil_sizes() {
+        for s in ["250kcmil", "250 KCMIL", "250MCM"] {
+            assert_eq!(Standard::Awg.parse_size(s).unwrap(), WireSize::Kcmil(250));
+        }
+        assert_eq!(WireSize::Kcmil(500).area(), 500_000.0);
+    }
+
+    #[test]
+    fn stranded_sizes_match_table_8() {
+        // NEC Chapter 9 Table 8, uncoated copper at 75°C, Ω per 1000 ft
+        let resistance = |size: &str| {
+            gauge(size).resistance(Material::Copper, 75.0)
+        };
+        assert!((resistance("12") - 1.93).abs() < 0.01);
+        assert!((resistance("8") - 0.778).abs() < 0.005);
+        assert!((resistance("4/0") - 0.0608).abs() < 0.0005);
+        assert!((resistance("250kcmil") - 0.0515).abs() < 0.0005);
+    }
+
+    #[test]
+    fn default_gauges_skip_the_odd_sizes() {
+        let ids: Vec<String> = Standard::Awg
+            .default_gauges()
+            .iter()
+            .map(|gauge| gauge.size.id())
+            .take_while(|id| !id.ends_with("kcmil"))
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                "28", "26", "24", "22", "20", "18", "16", "14", "12", "10", "8", "6", "4", "3",
+                "2", "1", "0", "00", "000", "0000"
+            ]
+        );
+
+        // The odd sizes can still be requested
+        assert!(Standard::Awg.select_gauges(&["9", "13"]).is_ok());
+    }
+
+    #[test]
+    fn selects_only_sizes_in_the_table() {
+        assert!(Standard::Awg.select_gauges(&["4/0", "250kcmil"]).is_ok());
+        assert!(Standard::Awg.select_gauges(&["40"]).is_err());
+        assert!(Standard::Awg.select_gauges(&["7kcmil"]).is_err());
+        assert!(Standard::Metric.select_gauges(&["3"]).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+//! Wire gauge voltage drop calculator
+//!
+//! The voltage-drop engine behind the `wgrs` command-line tool. Describe a
+//! [`Circuit`], pick the gauges to evaluate and call [`calculate`] to get one
+//! [`DropResult`] per gauge.
+
+use std::fmt;
+
+mod ac;
+mod ampacity;
+mod gauge;
+mod load;
+mod material;
+mod network;
+mod project;
+mod segment;
+mod solve;
+mod system;
+mod tap;
+
+pub use ac::{AcParameters, Conduit, DEFAULT_POWER_FACTOR};
+pub use ampacity::{
+    adjustment_factor, ambient_correction_factor, ampacity, InsulationRating, AMBIENT_BASE,
+};
+pub use gauge::{
+    wire_gauges, Awg, Standard, WireGauge, WireSize, CMIL_PER_MM2, KCMIL_SIZES, METRIC_SIZES,
+};
+pub use load::{solve_load, Load, LoadResult, OperatingPoint};
+pub use material::{Material, BASE_TEMPERATURE};
+pub use network::{
+    size_network, solve_network, Branch, BranchResult, Network, NetworkResult, NodeLoad, NodeResult,
+};
+pub use project::{ExplicitSettings, Project, ProjectCircuit};
+pub use segment::{calculate_run, RunResult, Segment, SegmentResult, COMBINED_MAX_DROP};
+pub use solve::{
+    max_current, max_distance, source_voltage, CurrentLimit, CurrentResult, DistanceResult,
+    SourceVoltageResult,
+};
+pub use system::System;
+pub use tap::{calculate_taps, Tap, TapsResult};
+
+/// Default maximum acceptable voltage drop percentage
+pub const DEFAULT_MAX_DROP: f64 = 3.0;
+
+/// Default conductor temperature in °C
+pub const DEFAULT_TEMPERATURE: f64 = 75.0;
+
+/// Errors returned by the calculator
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A requested size that is not in the selected [`Standard`]'s table
+    InvalidGauge {
+        /// The size as it was given
+        size: String,
+        /// The standard the size was looked up in
+        standard: Standard,
+    },
+    /// An insulation rating that does not match any [`InsulationRating`]
+    InvalidInsulationRating(String),
+    /// A conduit name that does not match any [`Conduit`]
+    InvalidConduit(String),
+    /// A system name that does not match any [`System`]
+    InvalidSystem(String),
+    /// A standard name that does not match any [`Standard`]
+    InvalidStandard(String),
+    /// A material name that does not match any [`Material`]
+    InvalidMaterial(String),
+    /// A run segment that is not written as `length:gauge:current[:material]`
+    InvalidSegment(String),
+    /// A tap that is not written as `position:current`
+    InvalidTap(String),
+    /// A network that cannot be read or is not radial
+    InvalidNetwork(String),
+    /// A project file that cannot be read or describes an invalid circuit
+    InvalidProject(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidGauge { size, standard } => write!(
+                f,
+                "Invalid gauge: {}. Valid gauges are: {}",
+                size,
+                standard.gauges().iter().map(|gauge| gauge.size.id()).collect::<Vec<_>>().join(", ")
+            ),
+            Error::InvalidInsulationRating(rating) => {
+                write!(f, "Invalid insulation rating: {}. Valid ratings are: 60, 75, 90", rating)
+            }
+            Error::InvalidConduit(name) => {
+                write!(f, "Invalid conduit: {}. Valid conduits are: pvc, aluminum, steel", name)
+            }
+            Error::InvalidSystem(name) => write!(
+                f,
+                "Invalid system: {}. Valid systems are: {}",
+                name,
+                System::ALL.iter().map(System::id).collect::<Vec<_>>().join(", ")
+            ),
+            Error::InvalidStandard(name) => {
+                write!(f, "Invalid standard: {}. Valid standards are: awg, metric", name)
+            }
+            Error::InvalidMaterial(name) => write!(
+                f,
+                "Invalid material: {}. Valid materials are: {}",
+                name,
+                Material::ALL.iter().map(Material::id).collect::<Vec<_>>().join(", ")
+            ),
+            Error::InvalidSegment(segment) => write!(
+                f,
+                "Invalid segment: {}. Segments are written as length:gauge:current[:material], e.g. 100:2/0:100",
+                segment
+            ),
+            Error::InvalidTap(tap) => write!(
+                f,
+                "Invalid tap: {}. Taps are written as position:current, e.g. 25:0.5",
+                tap
+            ),
+            Error::InvalidNetwork(reason) => write!(f, "Invalid network: {}", reason),
+            Error::InvalidProject(reason) => write!(f, "Invalid project: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A circuit to evaluate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circuit {
+    /// Voltage in volts, line-to-line for multiwire AC systems
+    pub voltage: f64,
+    /// Current in amps
+    pub current: f64,
+    /// One-way distance in feet
+    pub distance: f64,
+    /// Maximum acceptable voltage drop percentage
+    pub max_drop: f64,
+    /// Lowest source voltage in volts, such as a discharged battery; the drop
+    /// is evaluated at this voltage when set
+    pub voltage_min: Option<f64>,
+    /// Minimum voltage in volts the load needs to operate; when set it
+    /// replaces the `max_drop` percentage as the voltage drop check
+    pub min_load_voltage: Option<f64>,
+    /// Conductor material
+    pub material: Material,
+    /// Conductor temperature in °C
+    pub temperature: f64,
+    /// Insulation temperature rating used for the ampacity check
+    pub insulation_rating: InsulationRating,
+    /// Ambient temperature in °C for the ampacity correction
+    pub ambient: f64,
+    /// Number of current-carrying conductors in the raceway or cable
+    pub conductors_in_raceway: u32,
+    /// Electrical system
+    pub system: System,
+    /// AC parameters, or `None` for a purely resistive calculation
+    pub ac: Option<AcParameters>,
+}
+
+impl Circuit {
+    /// Create a copper DC circuit at [`DEFAULT_TEMPERATURE`] with 75°C
+    /// insulation, no ampacity derating and the default maximum drop of
+    /// [`DEFAULT_MAX_DROP`]
+    pub fn new(voltage: f64, current: f64, distance: f64) -> Self {
+        Circuit {
+            voltage,
+            current,
+            distance,
+            max_drop: DEFAULT_MAX_DROP,
+            voltage_min: None,
+            min_load_voltage: None,
+            material: Material::default(),
+            temperature: DEFAULT_TEMPERATURE,
+            insulation_rating: InsulationRating::default(),
+            ambient: AMBIENT_BASE,
+            conductors_in_raceway: 3,
+            system: System::default(),
+            ac: None,
+        }
+    }
+
+    /// Source voltage the drop is evaluated at: `voltage_min` if set,
+    /// otherwise `voltage`
+    pub fn evaluation_voltage(&self) -> f64 {
+        self.voltage_min.unwrap_or(self.voltage)
+    }
+
+    /// Combined ampacity derating factor for the ambient temperature and
+    /// the number of conductors in the raceway
+    pub fn derating_factor(&self) -> f64 {
+        ambient_correction_factor(self.ambient, self.insulation_rating)
+            * adjustment_factor(self.conductors_in_raceway)
+    }
+
+    /// Conductor resistance in ohms per 1000 feet, the AC resistance for AC
+    /// circuits
+    pub fn resistance(&self, gauge: &WireGauge) -> f64 {
+        let resistance = gauge.resistance(self.material, self.temperature);
+        match self.ac {
+            Some(ac) => ac.resistance(gauge, resistance),
+            None => resistance,
+        }
+    }
+
+    /// Effective impedance in ohms per 1000 feet, equal to the resistance
+    /// for DC circuits
+    pub fn impedance(&self, gauge: &WireGauge) -> f64 {
+        let resistance = self.resistance(gauge);
+
+        // AC circuits use the effective impedance: Z = R * cos θ + X * sin θ
+        match self.ac {
+            Some(ac) => ac.effective_impedance(resistance, ac.reactance(gauge)),
+            None => resistance,
+        }
+    }
+
+    /// NEC 310.16 ampacity of `gauge` for this circuit's material and
+    /// insulation, before derating
+    pub fn ampacity(&self, gauge: &WireGauge) -> Option<f64> {
+        ampacity(gauge, self.material, self.insulation_rating)
+    }
+
+    /// Ampacity of `gauge` in amps after ambient and bundling derating
+    pub fn derated_ampacity(&self, gauge: &WireGauge) -> Option<f64> {
+        self.ampacity(gauge).map(|amps| amps * self.derating_factor())
+    }
+
+    /// Ampacity check of `gauge` carrying `current` amps: the NEC 310.16
+    /// ampacity, the derated ampacity and whether the current is within it
+    ///
+    /// Sizes NEC 310.16 lists no ampacity for fail the check.
+    pub fn check_ampacity(&self, gauge: &WireGauge, current: f64) -> (Option<f64>, Option<f64>, bool) {
+        let ampacity = self.ampacity(gauge);
+        let derated_ampacity = self.derated_ampacity(gauge);
+        let within_ampacity = derated_ampacity.is_some_and(|amps| current <= amps);
+        (ampacity, derated_ampacity, within_ampacity)
+    }
+
+    /// Multiplier from volts times amps to real power in watts: the
+    /// system's phase factor, times the power factor for AC circuits
+    pub fn power_multiplier(&self) -> f64 {
+        self.system.phase_factor() * self.ac.map_or(1.0, |ac| ac.power_factor)
+    }
+
+    /// Real power in watts at `voltage` volts (line-to-line) and `current`
+    /// amps per line
+    pub fn real_power(&self, voltage: f64, current: f64) -> f64 {
+        voltage * current * self.power_multiplier()
+    }
+
+    /// Power in watts lost as heat in the conductors of `gauge` at the
+    /// circuit's current
+    pub fn wiring_loss(&self, gauge: &WireGauge) -> f64 {
+        self.current * self.current * self.resistance(gauge) * self.conductor_length() / 1000.0
+    }
+
+    /// Length in feet of all the conductors carrying the load current: the
+    /// one-way distance times the system's loaded conductors
+    pub fn conductor_length(&self) -> f64 {
+        self.distance * self.system.loaded_conductors() as f64
+    }
+
+    /// Effective conductor length in feet: the one-way distance times the
+    /// system's drop factor (round trip for two-wire circuits)
+    pub fn total_distance(&self) -> f64 {
+        self.distance * self.system.drop_factor()
+    }
+}
+
+/// Voltage drop for a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Total resistance of the wire run in ohms
+    pub total_resistance: f64,
+    /// Effective impedance in ohms per 1000 feet, equal to the resistance
+    /// for DC circuits
+    pub impedance: f64,
+    /// Voltage drop in volts
+    pub voltage_drop: f64,
+    /// Voltage drop as a percentage of the circuit's evaluation voltage
+    pub drop_percentage: f64,
+    /// Voltage at the load in volts, from the evaluation voltage
+    pub load_voltage: f64,
+    /// Power lost as heat in the conductors in watts (I²R)
+    pub power_loss: f64,
+    /// Power lost as a percentage of the real power sent from the source
+    pub power_loss_percentage: f64,
+    /// Power lost per foot of conductor in watts, zero for a run of no length
+    pub loss_per_foot: f64,
+    /// Ampacity in amps, or `None` if NEC 310.16 lists none for this size
+    pub ampacity: Option<f64>,
+    /// Ampacity after ambient and bundling derating, in amps
+    pub derated_ampacity: Option<f64>,
+    /// Whether the drop is within the circuit's limit: the load voltage
+    /// stays at or above `min_load_voltage` when set, otherwise the
+    /// percentage is within `max_drop`
+    pub within_max_drop: bool,
+    /// Whether the current is within the gauge's derated ampacity; `false`
+    /// when NEC 310.16 lists no ampacity to check it against
+    pub within_ampacity: bool,
+    /// Whether the gauge passes both the voltage drop and ampacity checks
+    pub acceptable: bool,
+}
+
+/// Calculate the voltage drop of `circuit` for each of `gauges`
+pub fn calculate(circuit: &Circuit, gauges: &[WireGauge]) -> Vec<DropResult> {
+    let total_distance = circuit.total_distance();
+    let conductor_length = circuit.conductor_length();
+    let voltage = circuit.evaluation_voltage();
+
+    gauges
+        .iter()
+        .map(|gauge| {
+            let impedance = circuit.impedance(gauge);
+
+            // Calculate total resistance for the wire run
+            let total_resistance = (circuit.resistance(gauge) * total_distance) / 1000.0;
+
+            // Calculate voltage drop using Ohm's law: V = I * Z
+            let voltage_drop = circuit.current * (impedance * total_distance) / 1000.0;
+
+            // Calculate percentage drop
+            let drop_percentage = (voltage_drop / voltage) * 100.0;
+            let load_voltage = voltage - voltage_drop;
+
+            // I²R loss over every loaded conductor of the run
+            let power_loss = circuit.wiring_loss(gauge);
+            let power_loss_percentage = power_loss / circuit.real_power(voltage, circuit.current) * 100.0;
+            let loss_per_foot = if conductor_length > 0.0 {
+                power_loss / conductor_length
+            } else {
+                0.0
+            };
+
+            let within_max_drop = match circuit.min_load_voltage {
+                Some(min_load_voltage) => load_voltage >= min_load_voltage,
+                None => drop_percentage <= circuit.max_drop,
+            };
+            let (ampacity, derated_ampacity, within_ampacity) =
+                circuit.check_ampacity(gauge, circuit.current);
+
+            DropResult {
+                gauge: *gauge,
+                total_resistance,
+                impedance,
+                voltage_drop,
+                drop_percentage,
+                load_voltage,
+                power_loss,
+                power_loss_percentage,
+                loss_per_foot,
+                ampacity,
+                derated_ampacity,
+                within_max_drop,
+                within_ampacity,
+                acceptable: within_max_drop && within_ampacity,
+            }
+        })
+        .collect()
+}
+
+/// The smallest acceptable gauge, assuming `results` run from smallest to largest
+pub fn recommended(results: &[DropResult]) -> Option<&DropResult> {
+    results.iter().find(|result| result.acceptable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gauge::gauge;
+
+    #[test]
+    fn checks_the_current_against_the_rating() {
+        let circuit = Circuit::new(120.0, 0.0, 0.0);
+        assert_eq!(circuit.check_ampacity(&gauge("12"), 25.0), (Some(25.0), Some(25.0), true));
+        assert!(!circuit.check_ampacity(&gauge("12"), 25.1).2);
+    }
+
+    #[test]
+    fn unrated_sizes_are_never_recommended() {
+        let circuit = Circuit::new(120.0, 20.0, 1.0);
+        assert_eq!(circuit.check_ampacity(&gauge("28"), 0.0), (None, None, false));
+
+        // Every size passes the drop over 1 ft; 14 AWG is the smallest
+        // rated for 20 A at 75°C
+        let results = calculate(&circuit, Standard::Awg.default_gauges());
+        assert_eq!(recommended(&results).unwrap().gauge, gauge("14"));
+    }
+
+    #[test]
+    fn min_load_voltage_is_checked_at_the_minimum_source_voltage() {
+        // 10 A through 2 × 15 ft of 12 AWG drops 0.5795 V, from 11 V rather
+        // than the nominal 12 V
+        let circuit = Circuit {
+            voltage_min: Some(11.0),
+            ..Circuit::new(12.0, 10.0, 15.0)
+        };
+        let result = calculate(&circuit, &[gauge("12")]).remove(0);
+        assert!((result.load_voltage - 10.4206).abs() < 1e-4);
+        assert!((result.drop_percentage - 5.268).abs() < 1e-3);
+        assert!(!result.within_max_drop);
+
+        // The load voltage passes at exactly the minimum, and fails just above it
+        let check = |min_load_voltage: f64| {
+            let circuit = Circuit {
+                min_load_voltage: Some(min_load_voltage),
+                ..circuit.clone()
+            };
+            calculate(&circuit, &[gauge("12")]).remove(0)
+        };
+        let at_minimum = check(result.load_voltage);
+        assert!(at_minimum.within_max_drop);
+        assert!(at_minimum.acceptable);
+        let below_minimum = check(result.load_voltage + 0.001);
+        assert!(!below_minimum.within_max_drop);
+        assert!(!below_minimum.acceptable);
+    }
+
+    #[test]
+    fn power_loss_is_i_squared_r_over_the_loaded_conductors() {
+        // 10 A through 2 × 100 ft of 12 AWG at 1.9315 Ω/1000 ft: 38.63 W
+        let result = calculate(&Circuit::new(120.0, 10.0, 100.0), &[gauge("12")]).remove(0);
+        assert!((result.power_loss - 38.63).abs() < 0.01);
+        assert!((result.power_loss_percentage - 3.219).abs() < 0.001);
+        assert!((result.loss_per_foot - 0.1932).abs() < 0.0001);
+    }
+
+    #[test]
+    fn a_run_of_no_length_has_no_loss() {
+        let result = calculate(&Circuit::new(120.0, 10.0, 0.0), &[gauge("12")]).remove(0);
+        assert_eq!(result.power_loss, 0.0);
+        assert_eq!(result.loss_per_foot, 0.0);
+    }
+}
@@ -0,0 +1,220 @@
+//! Load models and the operating point they settle at on each gauge
+
+use std::fmt;
+
+use crate::{calculate, Circuit, DropResult, WireGauge};
+
+/// How the load draws current
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Load {
+    /// A fixed current in amps, whatever the voltage
+    Current(f64),
+    /// A constant power in watts, like a switch-mode supply: the current
+    /// rises as the voltage sags
+    Power(f64),
+    /// A fixed resistance in ohms, like a heater, lamp or solenoid; per
+    /// phase of a wye-connected load on three-phase systems
+    Resistance(f64),
+}
+
+impl Load {
+    /// A resistive load rated at `watts` when run at `volts`
+    pub fn from_rating(watts: f64, volts: f64) -> Self {
+        Load::Resistance(volts * volts / watts)
+    }
+
+    /// Operating current in amps from a source of `voltage` volts through a
+    /// run that drops `loop_impedance` volts per amp, or `None` if the load
+    /// collapses the voltage
+    fn operating_current(&self, circuit: &Circuit, voltage: f64, loop_impedance: f64) -> Option<f64> {
+        match *self {
+            Load::Current(current) => Some(current),
+            Load::Power(power) => {
+                // P = k * (V - I * Z) * I, solved for the smaller (stable) I
+                let power = power / circuit.power_multiplier();
+                if loop_impedance == 0.0 {
+                    return Some(power / voltage);
+                }
+                let discriminant = voltage * voltage - 4.0 * loop_impedance * power;
+                (discriminant >= 0.0)
+                    .then(|| (voltage - discriminant.sqrt()) / (2.0 * loop_impedance))
+            }
+            Load::Resistance(resistance) => {
+                // Voltage divider between the run and the load; a wye load
+                // sees the line-to-neutral voltage, hence the phase factor
+                Some(voltage / (resistance * circuit.system.phase_factor() + loop_impedance))
+            }
+        }
+    }
+
+    /// Real power in watts the load takes at `load_voltage` and `current`
+    fn power(&self, circuit: &Circuit, load_voltage: f64, current: f64) -> f64 {
+        match *self {
+            Load::Current(_) | Load::Power(_) => circuit.real_power(load_voltage, current),
+            Load::Resistance(resistance) => {
+                current * current * resistance * circuit.system.phase_factor().powi(2)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Load {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Load::Current(current) => write!(f, "{} A", current),
+            Load::Power(power) => write!(f, "{} W constant power", power),
+            Load::Resistance(resistance) => write!(f, "{:.3} Ω resistance", resistance),
+        }
+    }
+}
+
+/// Where a load settles on a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatingPoint {
+    /// Current drawn in amps
+    pub current: f64,
+    /// Real power delivered to the load in watts
+    pub load_power: f64,
+    /// The voltage drop at that current
+    pub drop: DropResult,
+}
+
+/// Load model result for a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Total resistance of the wire run in ohms
+    pub total_resistance: f64,
+    /// The operating point, or `None` if no stable operating point exists
+    /// (voltage collapse)
+    pub operating_point: Option<OperatingPoint>,
+}
+
+impl LoadResult {
+    /// Whether the load operates on this gauge and it passes both the
+    /// voltage drop and ampacity checks
+    pub fn acceptable(&self) -> bool {
+        self.operating_point
+            .as_ref()
+            .is_some_and(|point| point.drop.acceptable)
+    }
+}
+
+/// Solve where `load` settles on each of `gauges` when fed by `circuit`
+///
+/// The circuit's own `current` is ignored; each gauge is evaluated at the
+/// current the load actually draws through it.
+pub fn solve_load(circuit: &Circuit, load: Load, gauges: &[WireGauge]) -> Vec<LoadResult> {
+    let voltage = circuit.evaluation_voltage();
+    let total_distance = circuit.total_distance();
+
+    gauges
+        .iter()
+        .map(|gauge| {
+            let loop_impedance = circuit.impedance(gauge) * total_distance / 1000.0;
+            let operating_point = load
+                .operating_current(circuit, voltage, loop_impedance)
+                .map(|current| {
+                    let circuit = Circuit {
+                        current,
+                        ..circuit.clone()
+                    };
+                    let drop = calculate(&circuit, &[*gauge]).remove(0);
+                    OperatingPoint {
+                        current,
+                        load_power: load.power(&circuit, drop.load_voltage, current),
+                        drop,
+                    }
+                });
+
+            LoadResult {
+                gauge: *gauge,
+                total_resistance: circuit.resistance(gauge) * total_distance / 1000.0,
+                operating_point,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AcParameters, Standard, System};
+
+    #[test]
+    fn constant_power_collapses_past_v_squared_over_4z() {
+        // V² = 4ZP at 10 V through 1 Ω with 25 W: one operating point, at V / 2
+        let circuit = Circuit::new(10.0, 0.0, 0.0);
+        assert_eq!(Load::Power(25.0).operating_current(&circuit, 10.0, 1.0), Some(5.0));
+        assert_eq!(Load::Power(25.001).operating_current(&circuit, 10.0, 1.0), None);
+
+        // Below the boundary, the stable (smaller) root
+        let current = Load::Power(16.0).operating_current(&circuit, 10.0, 1.0).unwrap();
+        assert!((current - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn collapse_boundary_includes_the_phase_and_power_factors() {
+        let circuit = Circuit {
+            system: System::ThreePhase3Wire,
+            ac: Some(AcParameters {
+                power_factor: 0.8,
+                ..AcParameters::default()
+            }),
+            ..Circuit::new(10.0, 0.0, 0.0)
+        };
+        let boundary = 25.0 * 3f64.sqrt() * 0.8;
+        assert!(Load::Power(boundary * 0.999).operating_current(&circuit, 10.0, 1.0).is_some());
+        assert!(Load::Power(boundary * 1.001).operating_current(&circuit, 10.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn collapsed_gauges_have_no_operating_point() {
+        let circuit = Circuit::new(12.0, 0.0, 100.0);
+        let gauges = Standard::Awg.select_gauges(&["4"]).unwrap();
+        let result = solve_load(&circuit, Load::Power(2000.0), &gauges).remove(0);
+        assert_eq!(result.operating_point, None);
+        assert!(!result.acceptable());
+    }
+
+    #[test]
+    fn resistance_divides_the_voltage_with_the_run() {
+        // 1 Ω load through 2 × 50 ft of 12 AWG at 1.9315 Ω/1000 ft (0.19315 Ω):
+        // 12 V / 1.19315 Ω = 10.057 A, leaving 10.057 V and 101.15 W at the load
+        let circuit = Circuit::new(12.0, 0.0, 50.0);
+        let gauges = Standard::Awg.select_gauges(&["12"]).unwrap();
+        let result = solve_load(&circuit, Load::Resistance(1.0), &gauges).remove(0);
+        let point = result.operating_point.unwrap();
+        assert!((point.current - 10.0574).abs() < 1e-4);
+        assert!((point.drop.load_voltage - 10.0574).abs() < 1e-4);
+        assert!((point.load_power - 101.152).abs() < 1e-3);
+        assert!((point.drop.power_loss - point.current.powi(2) * 0.19315).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rating_gives_the_resistance_at_the_rated_voltage() {
+        assert_eq!(Load::from_rating(60.0, 12.0), Load::Resistance(2.4));
+        assert_eq!(Load::from_rating(1500.0, 120.0), Load::Resistance(9.6));
+    }
+
+    #[test]
+    fn three_phase_resistance_is_per_phase_at_the_line_to_neutral_voltage() {
+        // 12 Ω per phase on 208 V: 120.09 V across each, 10.007 A per line,
+        // and 3 × 120.09² / 12 = 3605.3 W in all
+        let circuit = Circuit {
+            system: System::ThreePhase3Wire,
+            ..Circuit::new(208.0, 0.0, 0.0)
+        };
+        let load = Load::Resistance(12.0);
+        let current = load.operating_current(&circuit, 208.0, 0.0).unwrap();
+        assert!((current - 10.0074).abs() < 1e-4);
+        assert!((load.power(&circuit, 208.0, current) - 3605.33).abs() < 0.01);
+    }
+
+    #[test]
+    fn zero_impedance_draws_p_over_v() {
+        let circuit = Circuit::new(12.0, 0.0, 0.0);
+        assert_eq!(Load::Power(120.0).operating_current(&circuit, 12.0, 0.0), Some(10.0));
+    }
+}
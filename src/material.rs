@@ -0,0 +1,100 @@
+//! Conductor materials
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Temperature at which [`Material::resistivity`] is specified, in °C
+pub const BASE_TEMPERATURE: f64 = 20.0;
+
+/// Conductor material
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Material {
+    /// Annealed copper
+    #[default]
+    Copper,
+    /// Aluminum (1350 alloy)
+    Aluminum,
+    /// Copper-clad aluminum (10% copper by volume)
+    CopperCladAluminum,
+    /// Tin-coated copper
+    TinnedCopper,
+}
+
+impl Material {
+    /// Every material, in the order they are listed to users
+    pub const ALL: &'static [Material] = &[
+        Material::Copper,
+        Material::Aluminum,
+        Material::CopperCladAluminum,
+        Material::TinnedCopper,
+    ];
+
+    /// Resistivity in ohm-circular mils per foot at 20°C
+    pub fn resistivity(&self) -> f64 {
+        match self {
+            // 100% IACS
+            Material::Copper => 10.371,
+            // 61.0% IACS
+            Material::Aluminum => 17.002,
+            // 62.9% IACS
+            Material::CopperCladAluminum => 16.488,
+            // 96.9% IACS
+            Material::TinnedCopper => 10.703,
+        }
+    }
+
+    /// Temperature coefficient of resistance per °C, referenced to 20°C
+    pub fn temperature_coefficient(&self) -> f64 {
+        match self {
+            Material::Copper | Material::TinnedCopper => 0.00393,
+            Material::Aluminum => 0.00403,
+            Material::CopperCladAluminum => 0.00396,
+        }
+    }
+
+    /// Resistivity in ohm-circular mils per foot at `temperature` °C
+    pub fn resistivity_at(&self, temperature: f64) -> f64 {
+        self.resistivity()
+            * (1.0 + self.temperature_coefficient() * (temperature - BASE_TEMPERATURE))
+    }
+
+    /// Name accepted on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            Material::Copper => "copper",
+            Material::Aluminum => "aluminum",
+            Material::CopperCladAluminum => "copper-clad-aluminum",
+            Material::TinnedCopper => "tinned-copper",
+        }
+    }
+}
+
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Material::Copper => "Copper",
+            Material::Aluminum => "Aluminum",
+            Material::CopperCladAluminum => "Copper-clad aluminum",
+            Material::TinnedCopper => "Tinned copper",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Material {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "copper" | "cu" => Ok(Material::Copper),
+            "aluminum" | "aluminium" | "al" => Ok(Material::Aluminum),
+            "copper-clad-aluminum" | "copper-clad-aluminium" | "cca" => {
+                Ok(Material::CopperCladAluminum)
+            }
+            "tinned-copper" | "tinned" => Ok(Material::TinnedCopper),
+            _ => Err(Error::InvalidMaterial(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,536 @@
+//! Radial distribution networks: a source feeding loads through a tree of
+//! branches
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{calculate, Circuit, DropResult, Error, Material, Standard, WireGauge};
+
+/// A conductor run between two nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Branch {
+    /// Node nearer the source
+    pub from: String,
+    /// Node further from the source
+    pub to: String,
+    /// One-way length in feet
+    pub distance: f64,
+    /// Conductor gauge
+    pub gauge: WireGauge,
+    /// Conductor material, or `None` for the circuit's material
+    pub material: Option<Material>,
+}
+
+/// A load drawing a fixed current at a node
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeLoad {
+    /// Node the load is connected to
+    pub node: String,
+    /// Current in amps
+    pub current: f64,
+}
+
+/// A radial network: every node but the source is fed by exactly one
+/// branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct Network {
+    /// Name of the source node
+    pub source: String,
+    /// Branches, ordered so that every branch comes after the one feeding it
+    pub branches: Vec<Branch>,
+    /// Loads on the nodes
+    pub loads: Vec<NodeLoad>,
+}
+
+// Layout of a network file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NetworkFile {
+    #[serde(default = "default_source")]
+    source: String,
+    #[serde(default)]
+    branch: Vec<BranchEntry>,
+    #[serde(default)]
+    load: Vec<NodeLoadEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BranchEntry {
+    from: String,
+    to: String,
+    length: f64,
+    gauge: String,
+    material: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeLoadEntry {
+    node: String,
+    current: f64,
+}
+
+fn default_source() -> String {
+    "source".to_string()
+}
+
+impl Network {
+    /// Parse a network from TOML, with gauges in `standard`
+    ///
+    /// ```toml
+    /// source = "panel"
+    ///
+    /// [[branch]]
+    /// from = "panel"
+    /// to = "garage"
+    /// length = 120
+    /// gauge = "2"
+    /// material = "aluminum"
+    ///
+    /// [[load]]
+    /// node = "garage"
+    /// current = 40
+    /// ```
+    pub fn from_toml(s: &str, standard: Standard) -> Result<Self, Error> {
+        let file: NetworkFile =
+            toml::from_str(s).map_err(|err| Error::InvalidNetwork(err.message().to_string()))?;
+
+        let branches = file
+            .branch
+            .into_iter()
+            .map(|entry| {
+                if entry.length < 0.0 {
+                    return Err(Error::InvalidNetwork(format!(
+                        "branch {} → {} has a negative length",
+                        entry.from, entry.to
+                    )));
+                }
+                Ok(Branch {
+                    from: entry.from,
+                    to: entry.to,
+                    distance: entry.length,
+                    gauge: standard.select_gauges(&[&entry.gauge])?.remove(0),
+                    material: entry.material.map(|material| material.parse()).transpose()?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let loads = file
+            .load
+            .into_iter()
+            .map(|entry| {
+                if entry.current < 0.0 {
+                    return Err(Error::InvalidNetwork(format!(
+                        "load on {} has a negative current",
+                        entry.node
+                    )));
+                }
+                Ok(NodeLoad {
+                    node: entry.node,
+                    current: entry.current,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Network::new(file.source, branches, loads)
+    }
+
+    /// Build a network, checking that the branches form a tree fed from
+    /// `source` and that every load is on one of its nodes
+    ///
+    /// The branches are reordered outwards from the source.
+    pub fn new(source: String, branches: Vec<Branch>, loads: Vec<NodeLoad>) -> Result<Self, Error> {
+        let mut fed: HashMap<&str, usize> = HashMap::new();
+        for (index, branch) in branches.iter().enumerate() {
+            if branch.to == source {
+                return Err(Error::InvalidNetwork(format!(
+                    "branch {} → {} feeds the source",
+                    branch.from, branch.to
+                )));
+            }
+            if fed.insert(&branch.to, index).is_some() {
+                return Err(Error::InvalidNetwork(format!(
+                    "node {} is fed by more than one branch",
+                    branch.to
+                )));
+            }
+        }
+
+        // Walk outwards from the source; anything left over is not connected
+        let mut order = Vec::with_capacity(branches.len());
+        let mut reached = vec![source.as_str()];
+        while let Some(node) = reached.pop() {
+            for (index, branch) in branches.iter().enumerate() {
+                if branch.from == node {
+                    order.push(index);
+                    reached.push(&branch.to);
+                }
+            }
+        }
+        if let Some(branch) = branches.iter().enumerate().find(|(index, _)| !order.contains(index)) {
+            return Err(Error::InvalidNetwork(format!(
+                "branch {} → {} is not fed from {}",
+                branch.1.from, branch.1.to, source
+            )));
+        }
+        if let Some(load) = loads
+            .iter()
+            .find(|load| load.node != source && !fed.contains_key(load.node.as_str()))
+        {
+            return Err(Error::InvalidNetwork(format!("load on unknown node {}", load.node)));
+        }
+
+        let branches = order.into_iter().map(|index| branches[index].clone()).collect();
+        Ok(Network {
+            source,
+            branches,
+            loads,
+        })
+    }
+
+    /// Every node, the source first and then in branch order
+    pub fn nodes(&self) -> Vec<&str> {
+        std::iter::once(self.source.as_str())
+            .chain(self.branches.iter().map(|branch| branch.to.as_str()))
+            .collect()
+    }
+
+    /// Current in amps through each branch: the loads on every node beyond it
+    pub fn branch_currents(&self) -> Vec<f64> {
+        let mut currents = vec![0.0; self.branches.len()];
+        let index: HashMap<&str, usize> = self
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(index, branch)| (branch.to.as_str(), index))
+            .collect();
+
+        for load in &self.loads {
+            // Add the load to every branch on the path back to the source
+            let mut node = load.node.as_str();
+            while let Some(&branch) = index.get(node) {
+                currents[branch] += load.current;
+                node = &self.branches[branch].from;
+            }
+        }
+        currents
+    }
+}
+
+/// Voltage drop of a single branch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchResult {
+    /// The branch evaluated
+    pub branch: Branch,
+    /// Current through the branch in amps
+    pub current: f64,
+    /// The branch's own voltage drop, as a percentage of the source voltage
+    pub drop: DropResult,
+}
+
+/// Voltage at a single node
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeResult {
+    /// Node name
+    pub node: String,
+    /// Voltage in volts at the node
+    pub voltage: f64,
+    /// Drop in volts from the source to the node
+    pub voltage_drop: f64,
+    /// Drop as a percentage of the source voltage
+    pub drop_percentage: f64,
+    /// Whether the drop is within the circuit's maximum
+    pub within_max_drop: bool,
+}
+
+/// Solved network
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkResult {
+    /// Each branch, in the network's order
+    pub branches: Vec<BranchResult>,
+    /// Each node, the source first
+    pub nodes: Vec<NodeResult>,
+    /// Whether every node is within the maximum drop and every branch
+    /// within its ampacity
+    pub acceptable: bool,
+}
+
+/// Solve the node voltages and branch currents of `network` fed at the
+/// voltage of `circuit`
+///
+/// Every node is checked against the circuit's `max_drop` and every branch
+/// against its ampacity. The circuit supplies the voltage and installation;
+/// its own `current`, `distance` and `min_load_voltage` are ignored.
+pub fn solve_network(circuit: &Circuit, network: &Network) -> NetworkResult {
+    let voltage = circuit.evaluation_voltage();
+    let currents = network.branch_currents();
+
+    let mut node_drops: HashMap<&str, f64> = HashMap::from([(network.source.as_str(), 0.0)]);
+    let branches: Vec<BranchResult> = network
+        .branches
+        .iter()
+        .zip(currents)
+        .map(|(branch, current)| {
+            let branch_circuit = Circuit {
+                current,
+                distance: branch.distance,
+                material: branch.material.unwrap_or(circuit.material),
+                min_load_voltage: None,
+                ..circuit.clone()
+            };
+            let drop = calculate(&branch_circuit, &[branch.gauge]).remove(0);
+
+            // Branches come after their feeder, so its drop is known
+            let upstream = node_drops.get(branch.from.as_str()).copied().unwrap_or(0.0);
+            node_drops.insert(&branch.to, upstream + drop.voltage_drop);
+
+            BranchResult {
+                branch: branch.clone(),
+                current,
+                drop,
+            }
+        })
+        .collect();
+
+    let nodes: Vec<NodeResult> = network
+        .nodes()
+        .into_iter()
+        .map(|node| {
+            let voltage_drop = node_drops.get(node).copied().unwrap_or(0.0);
+            let drop_percentage = voltage_drop / voltage * 100.0;
+            NodeResult {
+                node: node.to_string(),
+                voltage: voltage - voltage_drop,
+                voltage_drop,
+                drop_percentage,
+                within_max_drop: drop_percentage <= circuit.max_drop,
+            }
+        })
+        .collect();
+
+    NetworkResult {
+        acceptable: nodes.iter().all(|node| node.within_max_drop)
+            && branches.iter().all(|branch| branch.drop.within_ampacity),
+        branches,
+        nodes,
+    }
+}
+
+/// Suggest the smallest gauge from `gauges` for each branch of `network`
+/// that brings every node within the maximum drop
+///
+/// Each branch starts at the smallest gauge rated for its current, then
+/// the branch with the largest drop on the way to the worst node is
+/// upsized one step at a time. Returns the gauges in branch order, or
+/// `None` if `gauges`, assumed to run from smallest to largest, cannot
+/// meet the requirements.
+pub fn size_network(circuit: &Circuit, network: &Network, gauges: &[WireGauge]) -> Option<Vec<WireGauge>> {
+    let currents = network.branch_currents();
+
+    // Smallest gauge of each branch rated for its current; sizes NEC 310.16
+    // lists no ampacity for are never a starting point
+    let mut sizes = network
+        .branches
+        .iter()
+        .zip(&currents)
+        .map(|(branch, &current)| {
+            let circuit = Circuit {
+                material: branch.material.unwrap_or(circuit.material),
+                ..circuit.clone()
+            };
+            gauges
+                .iter()
+                .position(|gauge| {
+                    circuit.derated_ampacity(gauge).is_some_and(|amps| current <= amps)
+                })
+        })
+        .collect::<Option<Vec<usize>>>()?;
+
+    let feeders: HashMap<&str, usize> = network
+        .branches
+        .iter()
+        .enumerate()
+        .map(|(index, branch)| (branch.to.as_str(), index))
+        .collect();
+
+    loop {
+        let mut sized = network.clone();
+        for (branch, &size) in sized.branches.iter_mut().zip(&sizes) {
+            branch.gauge = gauges[size];
+        }
+        let result = solve_network(circuit, &sized);
+        if result.acceptable {
+            return Some(sized.branches.iter().map(|branch| branch.gauge).collect());
+        }
+
+        // Upsize the branch with the largest drop between the source and the
+        // worst node that still has a larger gauge available
+        let worst = result
+            .nodes
+            .iter()
+            .max_by(|a, b| a.voltage_drop.total_cmp(&b.voltage_drop))?;
+        let mut path = Vec::new();
+        let mut node = worst.node.as_str();
+        while let Some(&branch) = feeders.get(node) {
+            path.push(branch);
+            node = &network.branches[branch].from;
+        }
+        let upsize = path
+            .into_iter()
+            .filter(|&branch| sizes[branch] + 1 < gauges.len())
+            .max_by(|&a, &b| {
+                let drop = |branch: usize| result.branches[branch].drop.voltage_drop;
+                drop(a).total_cmp(&drop(b))
+            })?;
+        sizes[upsize] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gauge::gauge;
+
+    fn branch(from: &str, to: &str) -> Branch {
+        Branch {
+            from: from.to_string(),
+            to: to.to_string(),
+            distance: 50.0,
+            gauge: gauge("10"),
+            material: None,
+        }
+    }
+
+    fn build(branches: Vec<Branch>) -> Result<Network, Error> {
+        Network::new("source".to_string(), branches, Vec::new())
+    }
+
+    fn example() -> Network {
+        Network::from_toml(include_str!("../examples/network.toml"), Standard::Awg).unwrap()
+    }
+
+    #[test]
+    fn orders_branches_from_the_source() {
+        let network = build(vec![branch("a", "b"), branch("source", "a")]).unwrap();
+        assert_eq!(network.nodes(), ["source", "a", "b"]);
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let cycle = build(vec![
+            branch("source", "a"),
+            branch("a", "b"),
+            branch("b", "c"),
+            branch("c", "b"),
+        ]);
+        assert!(matches!(cycle, Err(Error::InvalidNetwork(_))));
+
+        let detached_cycle = build(vec![branch("source", "a"), branch("b", "c"), branch("c", "b")]);
+        assert!(matches!(detached_cycle, Err(Error::InvalidNetwork(_))));
+
+        let back_to_source = build(vec![branch("source", "a"), branch("a", "source")]);
+        assert!(matches!(back_to_source, Err(Error::InvalidNetwork(_))));
+    }
+
+    #[test]
+    fn rejects_disconnected_branches() {
+        let network = build(vec![branch("source", "a"), branch("b", "c")]);
+        assert!(matches!(network, Err(Error::InvalidNetwork(_))));
+    }
+
+    #[test]
+    fn rejects_nodes_fed_twice() {
+        let network = build(vec![branch("source", "a"), branch("source", "b"), branch("b", "a")]);
+        assert!(matches!(network, Err(Error::InvalidNetwork(_))));
+    }
+
+    #[test]
+    fn rejects_loads_on_unknown_nodes() {
+        let load = NodeLoad {
+            node: "nowhere".to_string(),
+            current: 10.0,
+        };
+        let network = Network::new("source".to_string(), vec![branch("source", "a")], vec![load]);
+        assert!(matches!(network, Err(Error::InvalidNetwork(_))));
+    }
+
+    #[test]
+    fn rejects_sizes_missing_from_the_table() {
+        for gauge in ["40", "7kcmil"] {
+            let toml = format!(
+                "[[branch]]\nfrom = \"source\"\nto = \"a\"\nlength = 10\ngauge = \"{}\"\n",
+                gauge
+            );
+            assert!(matches!(
+                Network::from_toml(&toml, Standard::Awg),
+                Err(Error::InvalidGauge { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_negative_lengths_and_currents() {
+        let branch = "[[branch]]\nfrom = \"source\"\nto = \"a\"\ngauge = \"10\"\n";
+        let negative_length = format!("{}length = -10\n", branch);
+        assert!(matches!(
+            Network::from_toml(&negative_length, Standard::Awg),
+            Err(Error::InvalidNetwork(_))
+        ));
+
+        let negative_current =
+            format!("{}length = 10\n[[load]]\nnode = \"a\"\ncurrent = -5\n", branch);
+        assert!(matches!(
+            Network::from_toml(&negative_current, Standard::Awg),
+            Err(Error::InvalidNetwork(_))
+        ));
+    }
+
+    #[test]
+    fn branch_currents_sum_the_loads_beyond() {
+        // garage 15 A, workshop 24 A and lights 6 A, all fed through the garage
+        assert_eq!(example().branch_currents(), [45.0, 24.0, 6.0]);
+    }
+
+    #[test]
+    fn sizes_every_node_within_the_maximum_drop() {
+        let circuit = Circuit::new(240.0, 0.0, 0.0);
+        let network = example();
+        let gauges = Standard::Awg.gauges();
+
+        let sizes = size_network(&circuit, &network, gauges).unwrap();
+        let mut sized = network.clone();
+        for (branch, gauge) in sized.branches.iter_mut().zip(sizes) {
+            branch.gauge = gauge;
+        }
+        let result = solve_network(&circuit, &sized);
+        assert!(result.acceptable);
+        assert!(result.nodes.iter().all(|node| node.drop_percentage <= circuit.max_drop));
+    }
+
+    #[test]
+    fn sizing_starts_from_a_gauge_rated_for_the_current() {
+        // 20 A over 1 ft: every size passes the drop, but 14 AWG is the
+        // smallest rated for 20 A at 75°C
+        let toml = "[[branch]]\nfrom = \"source\"\nto = \"a\"\nlength = 1\ngauge = \"10\"\n\
+                    [[load]]\nnode = \"a\"\ncurrent = 20\n";
+        let network = Network::from_toml(toml, Standard::Awg).unwrap();
+        let circuit = Circuit::new(120.0, 0.0, 0.0);
+        let sizes = size_network(&circuit, &network, Standard::Awg.default_gauges()).unwrap();
+        assert_eq!(sizes, Standard::Awg.select_gauges(&["14"]).unwrap());
+    }
+
+    #[test]
+    fn sizing_gives_up_when_no_gauge_is_large_enough() {
+        let circuit = Circuit::new(12.0, 0.0, 0.0);
+        let gauges = Standard::Awg.select_gauges(&["14", "12", "10"]).unwrap();
+        assert_eq!(size_network(&circuit, &example(), &gauges), None);
+
+        // Every gauge carries the current, but none keeps the drop in check
+        let circuit = Circuit {
+            max_drop: 0.001,
+            ..Circuit::new(240.0, 0.0, 0.0)
+        };
+        assert_eq!(size_network(&circuit, &example(), Standard::Awg.gauges()), None);
+    }
+}
@@ -0,0 +1,411 @@
+//! Project files: the named circuits of an installation, sharing defaults
+
+use serde::Deserialize;
+
+use crate::{AcParameters, Circuit, Error, Standard, System, WireGauge};
+
+/// A named circuit of a project
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectCircuit {
+    /// Circuit name
+    pub name: String,
+    /// The circuit, with the defaults and its own overrides applied
+    pub circuit: Circuit,
+    /// The gauge planned or installed, if the project gives one
+    pub gauge: Option<WireGauge>,
+}
+
+/// The settings of a project's base circuit that were given explicitly,
+/// such as on the command line
+///
+/// An explicit setting of the base circuit takes precedence over the file's
+/// defaults; a circuit's own settings still override it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExplicitSettings {
+    /// Maximum voltage drop percentage
+    pub max_drop: bool,
+    /// Conductor material
+    pub material: bool,
+    /// Conductor temperature
+    pub temperature: bool,
+    /// Insulation temperature rating
+    pub insulation_rating: bool,
+    /// Ambient temperature
+    pub ambient: bool,
+    /// Current-carrying conductors in the raceway
+    pub conductors_in_raceway: bool,
+    /// Electrical system
+    pub system: bool,
+    /// Whether the circuit is AC
+    pub ac: bool,
+    /// AC power factor
+    pub power_factor: bool,
+    /// AC conduit type
+    pub conduit: bool,
+}
+
+/// Every circuit of an installation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Project {
+    /// Circuits, in file order
+    pub circuits: Vec<ProjectCircuit>,
+}
+
+// Layout of a project file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectFile {
+    #[serde(default)]
+    defaults: Settings,
+    #[serde(default)]
+    circuit: Vec<Settings>,
+}
+
+// Settings shared by the defaults table and each circuit; a circuit's own
+// settings override the defaults
+#[derive(Deserialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    name: Option<String>,
+    voltage: Option<f64>,
+    current: Option<f64>,
+    distance: Option<f64>,
+    max_drop: Option<f64>,
+    material: Option<String>,
+    temperature: Option<f64>,
+    insulation_rating: Option<Text>,
+    ambient: Option<f64>,
+    conductors_in_raceway: Option<u32>,
+    system: Option<String>,
+    ac: Option<bool>,
+    power_factor: Option<f64>,
+    conduit: Option<String>,
+    gauge: Option<Text>,
+}
+
+// A value written either as a string or as a bare whole number, like
+// `gauge = 12` or `gauge = "1/0"`
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum Text {
+    String(String),
+    Number(u64),
+}
+
+impl Text {
+    fn into_string(self) -> String {
+        match self {
+            Text::String(s) => s,
+            Text::Number(number) => number.to_string(),
+        }
+    }
+}
+
+impl Settings {
+    /// These settings, falling back to `defaults` for any not given
+    fn or(self, defaults: &Settings) -> Settings {
+        let defaults = defaults.clone();
+        Settings {
+            name: self.name,
+            voltage: self.voltage.or(defaults.voltage),
+            current: self.current.or(defaults.current),
+            distance: self.distance.or(defaults.distance),
+            max_drop: self.max_drop.or(defaults.max_drop),
+            material: self.material.or(defaults.material),
+            temperature: self.temperature.or(defaults.temperature),
+            insulation_rating: self.insulation_rating.or(defaults.insulation_rating),
+            ambient: self.ambient.or(defaults.ambient),
+            conductors_in_raceway: self.conductors_in_raceway.or(defaults.conductors_in_raceway),
+            system: self.system.or(defaults.system),
+            ac: self.ac.or(defaults.ac),
+            power_factor: self.power_factor.or(defaults.power_factor),
+            conduit: self.conduit.or(defaults.conduit),
+            gauge: self.gauge.or(defaults.gauge),
+        }
+    }
+
+    /// These settings without the ones `explicit` marks as given elsewhere
+    fn without(self, explicit: &ExplicitSettings) -> Settings {
+        Settings {
+            max_drop: self.max_drop.filter(|_| !explicit.max_drop),
+            material: self.material.filter(|_| !explicit.material),
+            temperature: self.temperature.filter(|_| !explicit.temperature),
+            insulation_rating: self.insulation_rating.filter(|_| !explicit.insulation_rating),
+            ambient: self.ambient.filter(|_| !explicit.ambient),
+            conductors_in_raceway: self
+                .conductors_in_raceway
+                .filter(|_| !explicit.conductors_in_raceway),
+            system: self.system.filter(|_| !explicit.system),
+            ac: self.ac.filter(|_| !explicit.ac),
+            power_factor: self.power_factor.filter(|_| !explicit.power_factor),
+            conduit: self.conduit.filter(|_| !explicit.conduit),
+            ..self
+        }
+    }
+
+    /// The circuit these settings describe, starting from `base`
+    fn circuit(self, name: &str, base: &Circuit, standard: Standard) -> Result<ProjectCircuit, Error> {
+        let missing = |field: &str| Error::InvalidProject(format!("circuit {} has no {}", name, field));
+        let mut circuit = Circuit {
+            voltage: self.voltage.ok_or_else(|| missing("voltage"))?,
+            current: self.current.ok_or_else(|| missing("current"))?,
+            distance: self.distance.ok_or_else(|| missing("distance"))?,
+            ..base.clone()
+        };
+        if let Some(max_drop) = self.max_drop {
+            circuit.max_drop = max_drop;
+        }
+        if let Some(material) = self.material {
+            circuit.material = material.parse()?;
+        }
+        if let Some(temperature) = self.temperature {
+            circuit.temperature = temperature;
+        }
+        if let Some(insulation_rating) = self.insulation_rating {
+            circuit.insulation_rating = insulation_rating.into_string().parse()?;
+        }
+        if let Some(ambient) = self.ambient {
+            circuit.ambient = ambient;
+        }
+        if let Some(conductors_in_raceway) = self.conductors_in_raceway {
+            circuit.conductors_in_raceway = conductors_in_raceway;
+        }
+
+        // AC settings refine the base circuit's; turning AC on without a
+        // system means a single-phase AC circuit
+        circuit.ac = if self.ac.unwrap_or(base.ac.is_some()) {
+            let mut ac = base.ac.unwrap_or_default();
+            if let Some(power_factor) = self.power_factor {
+                ac.power_factor = power_factor;
+            }
+            if let Some(conduit) = self.conduit {
+                ac.conduit = conduit.parse()?;
+            }
+            Some(ac)
+        } else {
+            None
+        };
+        circuit.system = match self.system {
+            Some(system) => system.parse()?,
+            None if circuit.ac.is_some() && !base.system.is_ac() => System::SinglePhase2Wire,
+            None => base.system,
+        };
+        if let Some(AcParameters { power_factor, .. }) = circuit.ac {
+            if !circuit.system.is_ac() {
+                return Err(Error::InvalidProject(format!(
+                    "circuit {} uses AC on a DC system",
+                    name
+                )));
+            }
+            if !(power_factor > 0.0 && power_factor <= 1.0) {
+                return Err(Error::InvalidProject(format!(
+                    "circuit {} has a power factor outside 0 to 1",
+                    name
+                )));
+            }
+        }
+
+        // YAML reads `00`, `000` and `0000` as the number 0, so a bare zero
+        // could be any of the aught sizes
+        if let Some(Text::Number(0)) = self.gauge {
+            return Err(Error::InvalidProject(format!(
+                "circuit {} has gauge 0; write aught sizes as strings, like \"0\" or \"00\"",
+                name
+            )));
+        }
+        let gauge = self
+            .gauge
+            .map(|gauge| standard.select_gauges(&[gauge.into_string()]))
+            .transpose()?
+            .map(|mut gauges| gauges.remove(0));
+
+        Ok(ProjectCircuit {
+            name: name.to_string(),
+            circuit,
+            gauge,
+        })
+    }
+}
+
+impl Project {
+    /// Parse a project from TOML, with circuits built on `base` and gauges
+    /// in `standard`
+    ///
+    /// A circuit's own settings override the file's defaults, which override
+    /// `base`, except for the settings of `base` that `explicit` marks as
+    /// given: those override the defaults too.
+    ///
+    /// ```toml
+    /// [defaults]
+    /// voltage = 120
+    /// material = "copper"
+    /// max_drop = 3
+    ///
+    /// [[circuit]]
+    /// name = "Kitchen counter"
+    /// current = 20
+    /// distance = 60
+    /// gauge = 12
+    ///
+    /// [[circuit]]
+    /// name = "Shop subpanel"
+    /// voltage = 240
+    /// current = 60
+    /// distance = 150
+    /// material = "aluminum"
+    /// ```
+    pub fn from_toml(
+        s: &str,
+        base: &Circuit,
+        explicit: &ExplicitSettings,
+        standard: Standard,
+    ) -> Result<Self, Error> {
+        let file: ProjectFile =
+            toml::from_str(s).map_err(|err| Error::InvalidProject(err.message().to_string()))?;
+        Project::from_file(file, base, explicit, standard)
+    }
+
+    /// Parse a project from YAML, laid out like [`Project::from_toml`]
+    pub fn from_yaml(
+        s: &str,
+        base: &Circuit,
+        explicit: &ExplicitSettings,
+        standard: Standard,
+    ) -> Result<Self, Error> {
+        let file: ProjectFile =
+            serde_yaml::from_str(s).map_err(|err| Error::InvalidProject(err.to_string()))?;
+        Project::from_file(file, base, explicit, standard)
+    }
+
+    fn from_file(
+        file: ProjectFile,
+        base: &Circuit,
+        explicit: &ExplicitSettings,
+        standard: Standard,
+    ) -> Result<Self, Error> {
+        if file.defaults.name.is_some() {
+            return Err(Error::InvalidProject("the defaults cannot have a name".to_string()));
+        }
+        let defaults = file.defaults.without(explicit);
+
+        let circuits = file
+            .circuit
+            .into_iter()
+            .enumerate()
+            .map(|(index, settings)| {
+                let name = settings.name.clone().unwrap_or_else(|| format!("#{}", index + 1));
+                settings
+                    .or(&defaults)
+                    .circuit(&name, base, standard)
+                    .map_err(|err| match err {
+                        Error::InvalidProject(_) => err,
+                        err => Error::InvalidProject(format!("circuit {}: {}", name, err)),
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Project { circuits })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Material;
+
+    const PROJECT: &str = r#"
+        [defaults]
+        voltage = 120
+        material = "aluminum"
+        max_drop = 2
+        ac = false
+
+        [[circuit]]
+        name = "Bench"
+        current = 20
+        distance = 45
+
+        [[circuit]]
+        name = "Saw"
+        current = 15
+        distance = 30
+        material = "copper"
+        max_drop = 4
+    "#;
+
+    fn circuits(base: &Circuit, explicit: &ExplicitSettings) -> Vec<Circuit> {
+        Project::from_toml(PROJECT, base, explicit, Standard::Awg)
+            .unwrap()
+            .circuits
+            .into_iter()
+            .map(|circuit| circuit.circuit)
+            .collect()
+    }
+
+    #[test]
+    fn defaults_override_the_base_and_circuits_override_the_defaults() {
+        let base = Circuit {
+            temperature: 90.0,
+            ..Circuit::new(0.0, 0.0, 0.0)
+        };
+        let circuits = circuits(&base, &ExplicitSettings::default());
+        let bench = &circuits[0];
+        assert_eq!((bench.voltage, bench.current, bench.distance), (120.0, 20.0, 45.0));
+        assert_eq!(circuits[0].material, Material::Aluminum);
+        assert_eq!(circuits[0].max_drop, 2.0);
+        assert_eq!(circuits[0].temperature, 90.0);
+        assert_eq!(circuits[1].material, Material::Copper);
+        assert_eq!(circuits[1].max_drop, 4.0);
+    }
+
+    #[test]
+    fn explicit_settings_override_the_defaults_but_not_the_circuits() {
+        let base = Circuit {
+            material: Material::TinnedCopper,
+            max_drop: 5.0,
+            ac: Some(AcParameters::default()),
+            system: System::SinglePhase2Wire,
+            ..Circuit::new(0.0, 0.0, 0.0)
+        };
+        let explicit = ExplicitSettings {
+            material: true,
+            max_drop: true,
+            ac: true,
+            ..ExplicitSettings::default()
+        };
+        let circuits = circuits(&base, &explicit);
+        assert_eq!(circuits[0].material, Material::TinnedCopper);
+        assert_eq!(circuits[0].max_drop, 5.0);
+        assert!(circuits[0].ac.is_some());
+        assert_eq!(circuits[1].material, Material::Copper);
+        assert_eq!(circuits[1].max_drop, 4.0);
+    }
+
+    fn yaml_gauge(gauge: &str) -> Result<Option<WireGauge>, Error> {
+        let yaml = format!(
+            "circuit:\n  - name: Feeder\n    voltage: 240\n    current: 100\n    distance: 50\n    \
+             gauge: {}\n",
+            gauge
+        );
+        let base = Circuit::new(0.0, 0.0, 0.0);
+        let project = Project::from_yaml(&yaml, &base, &ExplicitSettings::default(), Standard::Awg)?;
+        Ok(project.circuits.into_iter().next().unwrap().gauge)
+    }
+
+    #[test]
+    fn reads_yaml_gauges_as_written() {
+        let awg = |size| Standard::Awg.select_gauges(&[size]).unwrap().pop();
+        assert_eq!(yaml_gauge("12").unwrap(), awg("12"));
+        assert_eq!(yaml_gauge("\"00\"").unwrap(), awg("00"));
+        assert_eq!(yaml_gauge("'0000'").unwrap(), awg("0000"));
+        assert_eq!(yaml_gauge("250kcmil").unwrap(), awg("250kcmil"));
+
+        // Zero-padded sizes are the aught sizes, never 0 AWG, and a bare 0
+        // must be quoted since some YAML parsers read 00 as 0 too
+        for aught in ["00", "000", "0000"] {
+            assert_eq!(yaml_gauge(aught).unwrap(), awg(aught));
+        }
+        assert!(matches!(yaml_gauge("0"), Err(Error::InvalidProject(_))));
+        assert_eq!(yaml_gauge("\"0\"").unwrap(), awg("0"));
+        assert!(yaml_gauge("12.5").is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Runs made of several segments in series, such as feeder and branch
+
+use crate::{calculate, Circuit, DropResult, Error, Material, Standard, WireGauge};
+
+/// NEC 210.19(A) and 215.2(A) informational note: combined feeder and
+/// branch circuit voltage drop percentage
+pub const COMBINED_MAX_DROP: f64 = 5.0;
+
+/// One leg of a multi-segment run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    /// One-way length in feet
+    pub distance: f64,
+    /// Conductor gauge
+    pub gauge: WireGauge,
+    /// Current carried by the segment in amps
+    pub current: f64,
+    /// Conductor material, or `None` for the circuit's material
+    pub material: Option<Material>,
+}
+
+impl Segment {
+    /// Parse a segment written as `length:gauge:current[:material]`, e.g.
+    /// `100:2/0:100` or `30:10:20:aluminum`, with the gauge in `standard`
+    pub fn parse(s: &str, standard: Standard) -> Result<Self, Error> {
+        let invalid = || Error::InvalidSegment(s.to_string());
+        let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+        if !(3..=4).contains(&parts.len()) {
+            return Err(invalid());
+        }
+
+        let distance: f64 = parts[0].parse().map_err(|_| invalid())?;
+        let gauge = standard.select_gauges(&[parts[1]])?.remove(0);
+        let current: f64 = parts[2].parse().map_err(|_| invalid())?;
+        if distance < 0.0 || current < 0.0 {
+            return Err(invalid());
+        }
+        let material = parts.get(3).map(|material| material.parse()).transpose()?;
+
+        Ok(Segment {
+            distance,
+            gauge,
+            current,
+            material,
+        })
+    }
+}
+
+/// Voltage drop of a single segment within a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentResult {
+    /// The segment evaluated
+    pub segment: Segment,
+    /// The segment's own voltage drop, as a percentage of the source voltage
+    pub drop: DropResult,
+    /// Drop in volts from the source to the end of this segment
+    pub cumulative_drop: f64,
+    /// Cumulative drop as a percentage of the source voltage
+    pub cumulative_percentage: f64,
+    /// Voltage in volts at the end of this segment
+    pub end_voltage: f64,
+}
+
+/// Voltage drop of a whole multi-segment run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResult {
+    /// Each segment, from the source outwards
+    pub segments: Vec<SegmentResult>,
+    /// Total drop in volts from the source to the end of the run
+    pub voltage_drop: f64,
+    /// Total drop as a percentage of the source voltage
+    pub drop_percentage: f64,
+    /// Whether the total drop is within the combined maximum
+    pub within_combined_max: bool,
+    /// Whether every segment is acceptable and the total drop is within the
+    /// combined maximum
+    pub acceptable: bool,
+}
+
+/// Calculate the drop of `segments` in series fed by `circuit`
+///
+/// Each segment is checked against the circuit's `max_drop` and its own
+/// ampacity, and the total against `combined_max_drop` percent. The
+/// circuit supplies the voltage and installation; its own `current`,
+/// `distance` and `min_load_voltage` are ignored.
+pub fn calculate_run(circuit: &Circuit, segments: &[Segment], combined_max_drop: f64) -> RunResult {
+    let voltage = circuit.evaluation_voltage();
+    let mut cumulative_drop = 0.0;
+
+    let segments: Vec<SegmentResult> = segments
+        .iter()
+        .map(|segment| {
+            let segment_circuit = Circuit {
+                current: segment.current,
+                distance: segment.distance,
+                material: segment.material.unwrap_or(circuit.material),
+                min_load_voltage: None,
+                ..circuit.clone()
+            };
+            let drop = calculate(&segment_circuit, &[segment.gauge]).remove(0);
+            cumulative_drop += drop.voltage_drop;
+
+            SegmentResult {
+                segment: *segment,
+                drop,
+                cumulative_drop,
+                cumulative_percentage: cumulative_drop / voltage * 100.0,
+                end_voltage: voltage - cumulative_drop,
+            }
+        })
+        .collect();
+
+    let drop_percentage = cumulative_drop / voltage * 100.0;
+    let within_combined_max = drop_percentage <= combined_max_drop;
+
+    RunResult {
+        acceptable: within_combined_max && segments.iter().all(|result| result.drop.acceptable),
+        segments,
+        voltage_drop: cumulative_drop,
+        drop_percentage,
+        within_combined_max,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_segments() {
+        let segment = Segment::parse("30:10:20:aluminum", Standard::Awg).unwrap();
+        assert_eq!(segment.distance, 30.0);
+        assert_eq!(segment.gauge.to_string(), "10 AWG");
+        assert_eq!(segment.current, 20.0);
+        assert_eq!(segment.material, Some(Material::Aluminum));
+        assert_eq!(Segment::parse("100:2/0:100", Standard::Awg).unwrap().material, None);
+    }
+
+    #[test]
+    fn rejects_malformed_segments() {
+        for s in ["100:2/0", "100:2/0:100:copper:extra", "x:12:10", "-10:12:10", "10:12:-5"] {
+            assert!(Segment::parse(s, Standard::Awg).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn accumulates_the_drop_of_each_segment() {
+        // 12 AWG copper at 75°C is 1.9315 Ω/1000 ft: 10 A over 2 × 100 ft
+        // drops 3.863 V, then 5 A over 2 × 50 ft drops 0.966 V more
+        let circuit = Circuit::new(120.0, 0.0, 0.0);
+        let segments = [
+            Segment::parse("100:12:10", Standard::Awg).unwrap(),
+            Segment::parse("50:12:5", Standard::Awg).unwrap(),
+        ];
+        let run = calculate_run(&circuit, &segments, COMBINED_MAX_DROP);
+        assert!((run.segments[0].cumulative_drop - 3.863).abs() < 0.001);
+        assert!((run.segments[1].drop.voltage_drop - 0.966).abs() < 0.001);
+        assert!((run.voltage_drop - 4.829).abs() < 0.001);
+        assert!((run.segments[1].end_voltage - 115.171).abs() < 0.001);
+        assert!((run.drop_percentage - 4.024).abs() < 0.001);
+        assert!(run.within_combined_max);
+        // The first segment alone is over the 3% branch maximum
+        assert!(!run.segments[0].drop.acceptable);
+        assert!(!run.acceptable);
+    }
+}
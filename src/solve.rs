@@ -0,0 +1,266 @@
+//! Inverse solvers: the voltage drop calculation solved for other unknowns
+
+use std::fmt;
+
+use crate::{calculate, Circuit, WireGauge};
+
+/// Longest run for a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Effective impedance in ohms per 1000 feet
+    pub impedance: f64,
+    /// Longest one-way distance in feet that keeps the drop within the
+    /// circuit's maximum, infinite when the circuit's current is zero
+    pub max_distance: f64,
+    /// Ampacity in amps, or `None` if NEC 310.16 lists none for this size
+    pub ampacity: Option<f64>,
+    /// Ampacity after ambient and bundling derating, in amps
+    pub derated_ampacity: Option<f64>,
+    /// Whether the current is within the gauge's derated ampacity
+    pub within_ampacity: bool,
+}
+
+/// Longest one-way distance for each of `gauges` that keeps the drop of
+/// `circuit` within its maximum
+///
+/// This is the drop calculation solved for distance; the circuit's own
+/// `distance` is ignored.
+pub fn max_distance(circuit: &Circuit, gauges: &[WireGauge]) -> Vec<DistanceResult> {
+    let allowed_drop = circuit.voltage * circuit.max_drop / 100.0;
+
+    gauges
+        .iter()
+        .map(|gauge| {
+            let impedance = circuit.impedance(gauge);
+
+            // V = I * Z * d * factor / 1000, solved for d
+            let max_distance = allowed_drop * 1000.0
+                / (circuit.current * impedance * circuit.system.drop_factor());
+
+            let (ampacity, derated_ampacity, within_ampacity) =
+                circuit.check_ampacity(gauge, circuit.current);
+
+            DistanceResult {
+                gauge: *gauge,
+                impedance,
+                max_distance,
+                ampacity,
+                derated_ampacity,
+                within_ampacity,
+            }
+        })
+        .collect()
+}
+
+/// Which requirement limits a gauge's maximum current
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrentLimit {
+    /// The maximum voltage drop
+    VoltageDrop,
+    /// The derated NEC 310.16 ampacity
+    Ampacity,
+}
+
+impl fmt::Display for CurrentLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CurrentLimit::VoltageDrop => "Voltage drop",
+            CurrentLimit::Ampacity => "Ampacity",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Largest current for a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurrentResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Effective impedance in ohms per 1000 feet
+    pub impedance: f64,
+    /// Largest current in amps that keeps the drop within the circuit's
+    /// maximum, infinite when the circuit's distance is zero
+    pub drop_limited_current: f64,
+    /// Ampacity in amps, or `None` if NEC 310.16 lists none for this size
+    pub ampacity: Option<f64>,
+    /// Ampacity after ambient and bundling derating, in amps
+    pub derated_ampacity: Option<f64>,
+    /// Largest current in amps meeting both the drop and ampacity limits;
+    /// the drop-limited current when there is no ampacity rating
+    pub max_current: f64,
+    /// The requirement that sets `max_current`
+    pub limit: CurrentLimit,
+}
+
+/// Largest current each of `gauges` can carry over the distance of
+/// `circuit` within its maximum drop and derated ampacity
+///
+/// This is the drop calculation solved for current; the circuit's own
+/// `current` is ignored.
+pub fn max_current(circuit: &Circuit, gauges: &[WireGauge]) -> Vec<CurrentResult> {
+    let allowed_drop = circuit.voltage * circuit.max_drop / 100.0;
+    let total_distance = circuit.total_distance();
+
+    gauges
+        .iter()
+        .map(|gauge| {
+            let impedance = circuit.impedance(gauge);
+
+            // V = I * Z * d / 1000, solved for I
+            let drop_limited_current = allowed_drop * 1000.0 / (impedance * total_distance);
+
+            let ampacity = circuit.ampacity(gauge);
+            let derated_ampacity = circuit.derated_ampacity(gauge);
+            let (max_current, limit) = match derated_ampacity {
+                Some(amps) if amps < drop_limited_current => (amps, CurrentLimit::Ampacity),
+                _ => (drop_limited_current, CurrentLimit::VoltageDrop),
+            };
+
+            CurrentResult {
+                gauge: *gauge,
+                impedance,
+                drop_limited_current,
+                ampacity,
+                derated_ampacity,
+                max_current,
+                limit,
+            }
+        })
+        .collect()
+}
+
+/// Source voltage needed by a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceVoltageResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Total resistance of the wire run in ohms
+    pub total_resistance: f64,
+    /// Voltage drop in volts
+    pub voltage_drop: f64,
+    /// Source voltage in volts that delivers the load voltage
+    pub source_voltage: f64,
+    /// Voltage drop as a percentage of the source voltage
+    pub drop_percentage: f64,
+    /// Ampacity in amps, or `None` if NEC 310.16 lists none for this size
+    pub ampacity: Option<f64>,
+    /// Ampacity after ambient and bundling derating, in amps
+    pub derated_ampacity: Option<f64>,
+    /// Whether the drop is within the circuit's maximum
+    pub within_max_drop: bool,
+    /// Whether the current is within the gauge's derated ampacity
+    pub within_ampacity: bool,
+    /// Whether the gauge passes both the voltage drop and ampacity checks
+    pub acceptable: bool,
+}
+
+/// Source voltage each of `gauges` needs to deliver the voltage of
+/// `circuit` at the load
+///
+/// The circuit's `voltage` is taken as the load voltage. The drop is the
+/// same as [`calculate`] gives for the run, and its percentage is relative
+/// to the source voltage.
+pub fn source_voltage(circuit: &Circuit, gauges: &[WireGauge]) -> Vec<SourceVoltageResult> {
+    calculate(circuit, gauges)
+        .into_iter()
+        .map(|result| {
+            let source_voltage = circuit.voltage + result.voltage_drop;
+            let drop_percentage = result.voltage_drop / source_voltage * 100.0;
+            let within_max_drop = drop_percentage <= circuit.max_drop;
+
+            SourceVoltageResult {
+                gauge: result.gauge,
+                total_resistance: result.total_resistance,
+                voltage_drop: result.voltage_drop,
+                source_voltage,
+                drop_percentage,
+                ampacity: result.ampacity,
+                derated_ampacity: result.derated_ampacity,
+                within_max_drop,
+                within_ampacity: result.within_ampacity,
+                acceptable: within_max_drop && result.within_ampacity,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Standard;
+
+    fn gauges(sizes: &[&str]) -> Vec<WireGauge> {
+        Standard::Awg.select_gauges(sizes).unwrap()
+    }
+
+    #[test]
+    fn max_distance_solves_the_drop_for_distance() {
+        // 3% of 12 V is 0.36 V; 10 A through 1.9315 Ω/1000 ft out and back
+        // reaches it at 9.32 ft
+        let circuit = Circuit::new(12.0, 10.0, 0.0);
+        let result = max_distance(&circuit, &gauges(&["12"])).remove(0);
+        assert!((result.max_distance - 9.319).abs() < 0.01);
+
+        // The drop at that distance is exactly the maximum
+        let at_limit = Circuit {
+            distance: result.max_distance,
+            ..circuit
+        };
+        let drop = calculate(&at_limit, &gauges(&["12"])).remove(0);
+        assert!((drop.drop_percentage - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn max_distance_uses_the_system_drop_factor() {
+        let single_phase = Circuit {
+            system: crate::System::SinglePhase2Wire,
+            ..Circuit::new(208.0, 20.0, 0.0)
+        };
+        let three_phase = Circuit {
+            system: crate::System::ThreePhase3Wire,
+            ..single_phase.clone()
+        };
+        let distance =
+            |circuit: &Circuit| max_distance(circuit, &gauges(&["10"])).remove(0).max_distance;
+        let ratio = distance(&three_phase) / distance(&single_phase);
+        assert!((ratio - 2.0 / 3f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn max_current_is_the_smaller_of_the_drop_and_ampacity_limits() {
+        // 3% of 120 V is 3.6 V over 2 × 80 ft of 1.9315 Ω/1000 ft: 11.65 A
+        let circuit = Circuit::new(120.0, 0.0, 80.0);
+        let result = max_current(&circuit, &gauges(&["12"])).remove(0);
+        assert!((result.drop_limited_current - 11.649).abs() < 0.01);
+        assert_eq!(result.max_current, result.drop_limited_current);
+        assert_eq!(result.limit, CurrentLimit::VoltageDrop);
+
+        // Over 10 ft the drop allows 93 A, but 12 AWG is rated 25 A at 75°C
+        let short = Circuit {
+            distance: 10.0,
+            ..circuit.clone()
+        };
+        let result = max_current(&short, &gauges(&["12"])).remove(0);
+        assert!(result.drop_limited_current > 90.0);
+        assert_eq!(result.max_current, 25.0);
+        assert_eq!(result.limit, CurrentLimit::Ampacity);
+
+        // Unrated sizes are limited by the drop alone
+        let result = max_current(&short, &gauges(&["28"])).remove(0);
+        assert_eq!(result.limit, CurrentLimit::VoltageDrop);
+    }
+
+    #[test]
+    fn source_voltage_adds_the_drop_to_the_load_voltage() {
+        // 12 AWG copper at 75°C is 1.9315 Ω/1000 ft: 10 A over 2 × 15 ft
+        // drops 0.579 V, 4.61% of the 12.579 V source
+        let circuit = Circuit::new(12.0, 10.0, 15.0);
+        let result = source_voltage(&circuit, &gauges(&["12"])).remove(0);
+        assert!((result.voltage_drop - 0.5795).abs() < 0.001);
+        assert!((result.source_voltage - 12.5795).abs() < 0.001);
+        assert!((result.drop_percentage - 4.607).abs() < 0.01);
+        assert!(!result.within_max_drop);
+    }
+}
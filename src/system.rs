@@ -0,0 +1,175 @@
+//! Circuit systems and their voltage drop factors
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// Electrical system the circuit belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum System {
+    /// Two-wire DC circuit
+    #[default]
+    Dc,
+    /// Single-phase AC, one ungrounded conductor and a neutral
+    SinglePhase2Wire,
+    /// Balanced 120/240 V split-phase multiwire circuit
+    SplitPhase,
+    /// Three-phase, three ungrounded conductors
+    ThreePhase3Wire,
+    /// Balanced three-phase wye with a neutral, e.g. 208Y/120 or 480Y/277
+    ThreePhase4Wire,
+}
+
+impl System {
+    /// Every system, in the order they are listed to users
+    pub const ALL: &'static [System] = &[
+        System::Dc,
+        System::SinglePhase2Wire,
+        System::SplitPhase,
+        System::ThreePhase3Wire,
+        System::ThreePhase4Wire,
+    ];
+
+    /// Multiplier from the one-way run impedance to the voltage drop,
+    /// relative to the line-to-line voltage
+    ///
+    /// Two-wire circuits carry the current out and back, so the drop is
+    /// over twice the distance. In balanced multiwire circuits the neutral
+    /// carries no current and only the ungrounded conductors contribute:
+    /// a 120/240 V split-phase circuit drops like a 240 V two-wire circuit,
+    /// and both three-phase systems use √3.
+    pub fn drop_factor(&self) -> f64 {
+        match self {
+            System::Dc | System::SinglePhase2Wire | System::SplitPhase => 2.0,
+            System::ThreePhase3Wire | System::ThreePhase4Wire => 3f64.sqrt(),
+        }
+    }
+
+    /// Multiplier from the line-to-line voltage times the line current to
+    /// the total volt-amps: √3 for three-phase systems, otherwise 1
+    pub fn phase_factor(&self) -> f64 {
+        match self {
+            System::Dc | System::SinglePhase2Wire | System::SplitPhase => 1.0,
+            System::ThreePhase3Wire | System::ThreePhase4Wire => 3f64.sqrt(),
+        }
+    }
+
+    /// Number of conductors that carry the load current; the neutral of a
+    /// balanced multiwire circuit carries none
+    pub fn loaded_conductors(&self) -> u32 {
+        match self {
+            System::Dc | System::SinglePhase2Wire | System::SplitPhase => 2,
+            System::ThreePhase3Wire | System::ThreePhase4Wire => 3,
+        }
+    }
+
+    /// Whether this is an AC system
+    pub fn is_ac(&self) -> bool {
+        *self != System::Dc
+    }
+
+    /// Line-to-neutral voltage for systems with a neutral shared between
+    /// phases, given the line-to-line `voltage`
+    pub fn line_to_neutral(&self, voltage: f64) -> Option<f64> {
+        match self {
+            System::SplitPhase => Some(voltage / 2.0),
+            System::ThreePhase4Wire => Some(voltage / 3f64.sqrt()),
+            System::Dc | System::SinglePhase2Wire | System::ThreePhase3Wire => None,
+        }
+    }
+
+    /// Name accepted on the command line
+    pub fn id(&self) -> &'static str {
+        match self {
+            System::Dc => "dc",
+            System::SinglePhase2Wire => "single-phase-2-wire",
+            System::SplitPhase => "split-phase-120/240",
+            System::ThreePhase3Wire => "three-phase-3-wire",
+            System::ThreePhase4Wire => "three-phase-4-wire",
+        }
+    }
+}
+
+impl fmt::Display for System {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            System::Dc => "DC",
+            System::SinglePhase2Wire => "Single-phase 2-wire",
+            System::SplitPhase => "Split-phase 120/240 V",
+            System::ThreePhase3Wire => "Three-phase 3-wire",
+            System::ThreePhase4Wire => "Three-phase 4-wire",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for System {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dc" => Ok(System::Dc),
+            "single-phase-2-wire" | "single-phase" | "1p2w" => Ok(System::SinglePhase2Wire),
+            "split-phase-120/240" | "split-phase" => Ok(System::SplitPhase),
+            "three-phase-3-wire" | "three-phase" | "3p3w" => Ok(System::ThreePhase3Wire),
+            "three-phase-4-wire" | "3p4w" => Ok(System::ThreePhase4Wire),
+            _ => Err(Error::InvalidSystem(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate, Circuit, Standard};
+
+    #[test]
+    fn three_phase_uses_root_3_and_multiwire_neutrals_carry_nothing() {
+        let root_3 = 3f64.sqrt();
+        assert_eq!(System::SplitPhase.drop_factor(), 2.0);
+        assert_eq!(System::ThreePhase4Wire.drop_factor(), root_3);
+        assert_eq!(System::SinglePhase2Wire.phase_factor(), 1.0);
+        assert_eq!(System::ThreePhase3Wire.phase_factor(), root_3);
+        assert_eq!(System::SplitPhase.loaded_conductors(), 2);
+        assert_eq!(System::ThreePhase4Wire.loaded_conductors(), 3);
+    }
+
+    #[test]
+    fn gives_the_line_to_neutral_voltage_where_there_is_a_neutral() {
+        assert_eq!(System::SplitPhase.line_to_neutral(240.0), Some(120.0));
+        let wye = System::ThreePhase4Wire.line_to_neutral(208.0).unwrap();
+        assert!((wye - 120.09).abs() < 0.01);
+        assert_eq!(System::ThreePhase3Wire.line_to_neutral(480.0), None);
+        assert_eq!(System::Dc.line_to_neutral(12.0), None);
+    }
+
+    #[test]
+    fn drop_and_loss_follow_the_system() {
+        let gauge = Standard::Awg.select_gauges(&["12"]).unwrap();
+        let drop = |system, voltage, current| {
+            let circuit = Circuit {
+                system,
+                ..Circuit::new(voltage, current, 100.0)
+            };
+            calculate(&circuit, &gauge).remove(0)
+        };
+
+        // 20 A × 1.9315 Ω/1000 ft × 2 × 100 ft, as a 240 V two-wire circuit
+        let split_phase = drop(System::SplitPhase, 240.0, 20.0);
+        assert!((split_phase.voltage_drop - 7.726).abs() < 0.001);
+        assert!((split_phase.drop_percentage - 3.219).abs() < 0.001);
+
+        // 10 A × 1.9315 Ω/1000 ft × √3 × 100 ft, and I²R in three conductors
+        let three_phase = drop(System::ThreePhase3Wire, 208.0, 10.0);
+        assert!((three_phase.voltage_drop - 3.345).abs() < 0.001);
+        assert!((three_phase.power_loss - 57.95).abs() < 0.01);
+    }
+
+    #[test]
+    fn ids_round_trip() {
+        for system in System::ALL {
+            assert_eq!(system.id().parse::<System>().unwrap(), *system);
+        }
+    }
+}
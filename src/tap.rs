@@ -0,0 +1,149 @@
+//! Loads tapped at points along a single run, such as LED strings and
+//! daisy-chained lights
+
+use std::str::FromStr;
+
+use crate::{Circuit, Error, WireGauge};
+
+/// A load tapped off the run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tap {
+    /// One-way distance in feet from the source
+    pub position: f64,
+    /// Current drawn at this point in amps
+    pub current: f64,
+}
+
+impl FromStr for Tap {
+    type Err = Error;
+
+    /// Parse a tap written as `position:current`, e.g. `25:0.5`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidTap(s.to_string());
+        let (position, current) = s.split_once(':').ok_or_else(invalid)?;
+        let position: f64 = position.trim().parse().map_err(|_| invalid())?;
+        let current: f64 = current.trim().parse().map_err(|_| invalid())?;
+        if position < 0.0 || current < 0.0 {
+            return Err(invalid());
+        }
+        Ok(Tap { position, current })
+    }
+}
+
+/// Voltage along a tapped run for a single gauge
+#[derive(Debug, Clone, PartialEq)]
+pub struct TapsResult {
+    /// The gauge evaluated
+    pub gauge: WireGauge,
+    /// Voltage in volts at each tap, in the order the taps were given
+    pub tap_voltages: Vec<f64>,
+    /// Index of the tap with the lowest voltage
+    pub worst_tap: usize,
+    /// Voltage drop in volts to the worst tap
+    pub voltage_drop: f64,
+    /// Drop to the worst tap as a percentage of the circuit's evaluation
+    /// voltage
+    pub drop_percentage: f64,
+    /// Ampacity in amps, or `None` if NEC 310.16 lists none for this size
+    pub ampacity: Option<f64>,
+    /// Ampacity after ambient and bundling derating, in amps
+    pub derated_ampacity: Option<f64>,
+    /// Whether the drop to the worst tap is within the circuit's maximum
+    pub within_max_drop: bool,
+    /// Whether the total current, which flows in the first span, is within
+    /// the gauge's derated ampacity
+    pub within_ampacity: bool,
+    /// Whether the gauge passes both the voltage drop and ampacity checks
+    pub acceptable: bool,
+}
+
+/// Calculate the voltage at each of `taps` along a run of each of `gauges`
+///
+/// Each span between taps carries the current of every tap beyond it. The
+/// circuit's own `current` and `distance` are ignored.
+pub fn calculate_taps(circuit: &Circuit, taps: &[Tap], gauges: &[WireGauge]) -> Vec<TapsResult> {
+    let voltage = circuit.evaluation_voltage();
+    let total_current: f64 = taps.iter().map(|tap| tap.current).sum();
+
+    let mut order: Vec<usize> = (0..taps.len()).collect();
+    order.sort_by(|&a, &b| taps[a].position.total_cmp(&taps[b].position));
+
+    gauges
+        .iter()
+        .map(|gauge| {
+            let impedance = circuit.impedance(gauge);
+
+            // Walk outwards from the source, dropping the remaining current
+            // over each span
+            let mut tap_voltages = vec![voltage; taps.len()];
+            let mut drop = 0.0;
+            let mut position = 0.0;
+            let mut current = total_current;
+            for &index in &order {
+                let span = taps[index].position - position;
+                drop += current * impedance * span * circuit.system.drop_factor() / 1000.0;
+                tap_voltages[index] = voltage - drop;
+                position = taps[index].position;
+                current -= taps[index].current;
+            }
+
+            let worst_tap = (0..taps.len())
+                .min_by(|&a, &b| tap_voltages[a].total_cmp(&tap_voltages[b]))
+                .unwrap_or(0);
+            let voltage_drop = voltage - tap_voltages.get(worst_tap).copied().unwrap_or(voltage);
+            let drop_percentage = voltage_drop / voltage * 100.0;
+
+            let within_max_drop = drop_percentage <= circuit.max_drop;
+            let (ampacity, derated_ampacity, within_ampacity) =
+                circuit.check_ampacity(gauge, total_current);
+
+            TapsResult {
+                gauge: *gauge,
+                tap_voltages,
+                worst_tap,
+                voltage_drop,
+                drop_percentage,
+                ampacity,
+                derated_ampacity,
+                within_max_drop,
+                within_ampacity,
+                acceptable: within_max_drop && within_ampacity,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Standard;
+
+    #[test]
+    fn parses_taps() {
+        let tap: Tap = " 25 : 0.5 ".parse().unwrap();
+        assert_eq!(tap, Tap { position: 25.0, current: 0.5 });
+        assert!("25".parse::<Tap>().is_err());
+        assert!("-5:1".parse::<Tap>().is_err());
+        assert!("10:-5".parse::<Tap>().is_err());
+    }
+
+    #[test]
+    fn each_span_carries_the_current_of_the_taps_beyond_it() {
+        // Given furthest first: 2 A over the first 10 ft and 1 A over the
+        // next, through 1.9315 Ω/1000 ft out and back
+        let circuit = Circuit::new(12.0, 0.0, 0.0);
+        let taps = [
+            Tap { position: 20.0, current: 1.0 },
+            Tap { position: 10.0, current: 1.0 },
+        ];
+        let gauges = Standard::Awg.select_gauges(&["12"]).unwrap();
+        let result = calculate_taps(&circuit, &taps, &gauges).remove(0);
+
+        assert!((result.tap_voltages[1] - 11.92274).abs() < 1e-4);
+        assert!((result.tap_voltages[0] - 11.88411).abs() < 1e-4);
+        assert_eq!(result.worst_tap, 0);
+        assert!((result.voltage_drop - 0.11589).abs() < 1e-4);
+        assert!((result.drop_percentage - 0.9658).abs() < 1e-3);
+        assert!(result.acceptable);
+    }
+}